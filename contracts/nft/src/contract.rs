@@ -0,0 +1,192 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+    Uint128,
+};
+
+use crate::{
+    error::ContractError,
+    msg::{
+        CheckRoyaltiesResponse, Cw2981QueryMsg, ExecuteMsg, Extension, InstantiateMsg, MigrateMsg,
+        NftExecuteMsg, QueryMsg, RoyaltiesInfoResponse, RoyaltyInfo,
+    },
+    state::{ROYALTIES, SOULBOUND},
+};
+
+const CONTRACT_NAME: &str = "crates.io:cosmos-adventures-nft";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub type NftContract<'a> =
+    cw721_base::Cw721Contract<'a, Extension, Empty, NftExecuteMsg, Cw2981QueryMsg>;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    SOULBOUND.save(deps.storage, &msg.soulbound)?;
+    let response = NftContract::default().instantiate(
+        deps.branch(),
+        env,
+        info,
+        cw721_base::InstantiateMsg {
+            name: msg.name,
+            symbol: msg.symbol,
+            minter: msg.minter,
+        },
+    )?;
+    // The base contract sets its own version
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(response.add_attribute("soulbound", msg.soulbound.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let contract = NftContract::default();
+    let soulbound = SOULBOUND.load(deps.storage)?;
+    match msg {
+        ExecuteMsg::Extension { msg } => execute_extension(deps, info, &contract, msg),
+        ExecuteMsg::Burn { token_id } => {
+            let response = if soulbound && info.sender == contract.minter.load(deps.storage)? {
+                burn_soulbound(deps.branch(), info, &contract, &token_id)?
+            } else {
+                contract.execute(
+                    deps.branch(),
+                    env,
+                    info,
+                    ExecuteMsg::Burn {
+                        token_id: token_id.clone(),
+                    },
+                )?
+            };
+            ROYALTIES.remove(deps.storage, &token_id);
+            Ok(response)
+        }
+        ExecuteMsg::TransferNft { .. }
+        | ExecuteMsg::SendNft { .. }
+        | ExecuteMsg::Approve { .. }
+        | ExecuteMsg::ApproveAll { .. }
+            if soulbound =>
+        {
+            Err(ContractError::Soulbound {})
+        }
+        msg => Ok(contract.execute(deps, env, info, msg)?),
+    }
+}
+
+/// Burns a soulbound token for its owner
+/// Its owner can't approve anyone, so the minter burns it without approval
+fn burn_soulbound(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: &NftContract,
+    token_id: &str,
+) -> Result<Response, ContractError> {
+    contract.tokens.load(deps.storage, token_id)?;
+    contract.tokens.remove(deps.storage, token_id)?;
+    contract.decrement_tokens(deps.storage)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "burn")
+        .add_attribute("sender", info.sender)
+        .add_attribute("token_id", token_id))
+}
+
+fn execute_extension(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: &NftContract,
+    msg: NftExecuteMsg,
+) -> Result<Response, ContractError> {
+    if info.sender != contract.minter.load(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match msg {
+        NftExecuteMsg::SetRoyalty { token_id, royalty } => {
+            // The token has to exist
+            contract.tokens.load(deps.storage, &token_id)?;
+            match royalty {
+                Some(royalty) => {
+                    if royalty.share > Decimal::one() {
+                        return Err(ContractError::InvalidRoyalty(royalty.share));
+                    }
+                    deps.api.addr_validate(&royalty.payment_address)?;
+                    ROYALTIES.save(deps.storage, &token_id, &royalty)?;
+                }
+                None => ROYALTIES.remove(deps.storage, &token_id),
+            }
+            Ok(Response::new()
+                .add_attribute("action", "set_royalty")
+                .add_attribute("token_id", token_id))
+        }
+        NftExecuteMsg::UpdateMetadata {
+            token_id,
+            token_uri,
+            extension,
+        } => {
+            let mut token = contract.tokens.load(deps.storage, &token_id)?;
+            token.token_uri = token_uri;
+            token.extension = extension;
+            contract.tokens.save(deps.storage, &token_id, &token)?;
+            Ok(Response::new()
+                .add_attribute("action", "update_metadata")
+                .add_attribute("token_id", token_id))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Extension { msg } => match msg {
+            Cw2981QueryMsg::RoyaltyInfo {
+                token_id,
+                sale_price,
+            } => to_json_binary(&query_royalty_info(deps, token_id, sale_price)?),
+            Cw2981QueryMsg::CheckRoyalties {} => to_json_binary(&CheckRoyaltiesResponse {
+                royalty_payments: true,
+            }),
+        },
+        msg => NftContract::default().query(deps, env, msg),
+    }
+}
+
+fn query_royalty_info(
+    deps: Deps,
+    token_id: String,
+    sale_price: Uint128,
+) -> StdResult<RoyaltiesInfoResponse> {
+    Ok(match ROYALTIES.may_load(deps.storage, &token_id)? {
+        Some(RoyaltyInfo {
+            payment_address,
+            share,
+        }) => RoyaltiesInfoResponse {
+            address: payment_address,
+            royalty_amount: sale_price * share,
+        },
+        None => RoyaltiesInfoResponse {
+            address: String::new(),
+            royalty_amount: Uint128::zero(),
+        },
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Contracts migrated from cw721-metadata-onchain keep their tokens, they have no royalties yet
+    // and their tokens stay transferable
+    if !SOULBOUND.exists(deps.storage) {
+        SOULBOUND.save(deps.storage, &false)?;
+    }
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new())
+}
@@ -0,0 +1,20 @@
+use cosmwasm_std::{Decimal, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Base(#[from] cw721_base::ContractError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Tokens of this collection are soulbound and can't be transferred")]
+    Soulbound {},
+
+    #[error("Royalty share {0} is above 1")]
+    InvalidRoyalty(Decimal),
+}
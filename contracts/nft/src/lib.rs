@@ -0,0 +1,10 @@
+//! cw721 contract of the hub collections
+//! Tokens keep their metadata on-chain, like cw721-metadata-onchain, and can carry a cw2981 royalty
+//! Tokens of soulbound collections can't be transferred, the minter can burn them for their owner
+
+pub mod contract;
+pub mod error;
+pub mod msg;
+pub mod state;
+
+pub use crate::error::ContractError;
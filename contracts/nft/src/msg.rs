@@ -0,0 +1,74 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{CustomMsg, Decimal, Uint128};
+pub use cw721_metadata_onchain::{Extension, Metadata};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    pub name: String,
+    pub symbol: String,
+    pub minter: String,
+    /// Tokens of soulbound collections can't be transferred or approved
+    /// Their owner can burn them, and so can the minter
+    #[serde(default)]
+    pub soulbound: bool,
+}
+
+/// Messages added to the cw721 ones, only the minter can send them
+#[cw_serde]
+pub enum NftExecuteMsg {
+    /// Sets the royalty paid on the sales of a token, removes it if `None`
+    SetRoyalty {
+        token_id: String,
+        royalty: Option<RoyaltyInfo>,
+    },
+    /// Replaces the token uri and the metadata of a token, without changing its owner or approvals
+    UpdateMetadata {
+        token_id: String,
+        token_uri: Option<String>,
+        extension: Extension,
+    },
+}
+
+impl CustomMsg for NftExecuteMsg {}
+
+/// cw2981 queries, sent as cw721 extension queries
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum Cw2981QueryMsg {
+    /// Royalty owed on the sale of a token for `sale_price`
+    /// The address is empty if the token has no royalty
+    #[returns(RoyaltiesInfoResponse)]
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+    /// Whether the contract implements royalties
+    #[returns(CheckRoyaltiesResponse)]
+    CheckRoyalties {},
+}
+
+impl CustomMsg for Cw2981QueryMsg {}
+
+pub type ExecuteMsg = cw721_base::ExecuteMsg<Extension, NftExecuteMsg>;
+pub type QueryMsg = cw721_base::QueryMsg<Cw2981QueryMsg>;
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+pub struct RoyaltyInfo {
+    pub payment_address: String,
+    /// Part of the sale price paid to `payment_address`, between 0 and 1
+    pub share: Decimal,
+}
+
+#[cw_serde]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    pub royalty_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct CheckRoyaltiesResponse {
+    pub royalty_payments: bool,
+}
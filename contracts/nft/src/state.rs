@@ -0,0 +1,8 @@
+use cw_storage_plus::{Item, Map};
+
+use crate::msg::RoyaltyInfo;
+
+/// Whether the tokens of the collection can't be transferred
+pub const SOULBOUND: Item<bool> = Item::new("soulbound");
+/// cw2981 royalty of the tokens that have one
+pub const ROYALTIES: Map<&str, RoyaltyInfo> = Map::new("royalties");
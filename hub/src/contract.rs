@@ -1,12 +1,12 @@
 use crate::ibc::{self, TRANSFER_CALLBACK};
-use crate::msg::HubMigrateMsg;
 use crate::{
     error::HubError,
     handlers,
     msg::{HubExecuteMsg, HubInstantiateMsg, HubQueryMsg},
+    replies,
 };
 use abstract_adapter::AdapterContract;
-use cosmwasm_std::Response;
+use cosmwasm_std::{Empty, Response};
 
 /// The version of your app
 pub const HUB_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -17,25 +17,158 @@ pub const HUB_ID: &str = "cosmos-adventures:hub";
 pub type HubResult<T = Response> = Result<T, HubError>;
 
 /// The type of the app that is used to build your app and access the Abstract SDK features.
-pub type Hub =
-    AdapterContract<HubError, HubInstantiateMsg, HubExecuteMsg, HubQueryMsg, HubMigrateMsg>;
+/// Adapters have no migrate handler, the hub migrations are handled by its own entry point
+pub type Hub = AdapterContract<HubError, HubInstantiateMsg, HubExecuteMsg, HubQueryMsg, Empty>;
 
 const HUB: Hub = Hub::new(HUB_ID, HUB_VERSION, None)
     .with_instantiate(handlers::instantiate_handler)
     .with_execute(handlers::execute_handler)
     .with_query(handlers::query_handler)
-    .with_replies(&[])
+    .with_replies(&[(replies::ARRIVAL_HOOK_REPLY_ID, replies::arrival_hook_reply)])
     .with_ibc_callbacks(&[(TRANSFER_CALLBACK, ibc::transfer::transfer_callback)])
     .with_module_ibc(ibc::module_ibc::receive_module_ibc);
 
 // Export handlers
+// The execute entry point also accepts cw721 hooks, so the endpoints are written out instead of using `export_endpoints!`
 #[cfg(feature = "export")]
-abstract_adapter::export_endpoints!(HUB, Hub);
+mod endpoints {
+    use abstract_sdk::base::{
+        ExecuteEndpoint, InstantiateEndpoint, QueryEndpoint, ReplyEndpoint, SudoEndpoint,
+    };
+    use cosmwasm_std::{
+        entry_point, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
+    };
+
+    use super::{HubResult, HUB};
+    use crate::{
+        handlers,
+        msg::{Cw721HookMsg, HubEntryExecuteMsg, HubMigrateMsg, InstantiateMsg, QueryMsg},
+    };
+
+    #[entry_point]
+    pub fn instantiate(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> HubResult<Response> {
+        HUB.instantiate(deps, env, info, msg)
+    }
+
+    #[entry_point]
+    pub fn execute(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: HubEntryExecuteMsg,
+    ) -> HubResult<Response> {
+        match msg {
+            HubEntryExecuteMsg::Cw721(Cw721HookMsg::ReceiveNft(receive_msg)) => {
+                handlers::receive_nft_handler(deps, env, info, HUB, receive_msg)
+            }
+            HubEntryExecuteMsg::Adapter(msg) => HUB.execute(deps, env, info, msg),
+        }
+    }
+
+    #[entry_point]
+    pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> HubResult<Binary> {
+        HUB.query(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> HubResult<Response> {
+        HUB.reply(deps, env, msg)
+    }
+
+    #[entry_point]
+    pub fn migrate(deps: DepsMut, env: Env, msg: HubMigrateMsg) -> HubResult<Response> {
+        handlers::migrate_handler(deps, env, HUB, msg)
+    }
+
+    #[entry_point]
+    pub fn sudo(deps: DepsMut, env: Env, msg: Empty) -> HubResult<Response> {
+        HUB.sudo(deps, env, msg)
+    }
+}
+#[cfg(feature = "export")]
+pub use endpoints::{execute, instantiate, migrate, query, reply, sudo};
+
+// ICS-721 handlers
+#[cfg(feature = "export")]
+mod ics721_endpoints {
+    use crate::ibc::ics721;
+    use cosmwasm_std::{
+        entry_point, DepsMut, Env, IbcBasicResponse, IbcChannelCloseMsg, IbcChannelConnectMsg,
+        IbcChannelOpenMsg, IbcChannelOpenResponse, IbcPacketAckMsg, IbcPacketReceiveMsg,
+        IbcPacketTimeoutMsg, IbcReceiveResponse,
+    };
+
+    use super::HubResult;
+
+    #[entry_point]
+    pub fn ibc_channel_open(
+        _deps: DepsMut,
+        _env: Env,
+        msg: IbcChannelOpenMsg,
+    ) -> HubResult<IbcChannelOpenResponse> {
+        ics721::channel_open(msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_channel_connect(
+        deps: DepsMut,
+        _env: Env,
+        msg: IbcChannelConnectMsg,
+    ) -> HubResult<IbcBasicResponse> {
+        ics721::channel_connect(deps, msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_channel_close(
+        deps: DepsMut,
+        _env: Env,
+        msg: IbcChannelCloseMsg,
+    ) -> HubResult<IbcBasicResponse> {
+        ics721::channel_close(deps, msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_packet_receive(
+        deps: DepsMut,
+        _env: Env,
+        msg: IbcPacketReceiveMsg,
+    ) -> HubResult<IbcReceiveResponse> {
+        ics721::packet_receive(deps, msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_packet_ack(
+        deps: DepsMut,
+        _env: Env,
+        msg: IbcPacketAckMsg,
+    ) -> HubResult<IbcBasicResponse> {
+        ics721::packet_ack(deps, msg)
+    }
+
+    #[entry_point]
+    pub fn ibc_packet_timeout(
+        deps: DepsMut,
+        _env: Env,
+        msg: IbcPacketTimeoutMsg,
+    ) -> HubResult<IbcBasicResponse> {
+        ics721::packet_timeout(deps, msg)
+    }
+}
+#[cfg(feature = "export")]
+pub use ics721_endpoints::{
+    ibc_channel_close, ibc_channel_connect, ibc_channel_open, ibc_packet_ack, ibc_packet_receive,
+    ibc_packet_timeout,
+};
 
 #[cfg(feature = "interface")]
 pub mod interface {
     use crate::msg::HubInstantiateMsg;
-    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+    use crate::msg::{ExecuteMsg, HubMigrateMsg, InstantiateMsg, QueryMsg};
     use abstract_interface::AdapterDeployer;
     use abstract_interface::RegisteredModule;
     use abstract_sdk::features::ModuleIdentification;
@@ -46,7 +179,7 @@ pub mod interface {
 
     use super::HUB;
 
-    #[interface(InstantiateMsg, ExecuteMsg, QueryMsg, Empty)]
+    #[interface(InstantiateMsg, ExecuteMsg, QueryMsg, HubMigrateMsg)]
     pub struct CosmosAdventuresHub<Chain>;
 
     // Implement deployer trait
@@ -60,7 +193,16 @@ pub mod interface {
                     crate::contract::instantiate,
                     crate::contract::query,
                 )
-                .with_reply(crate::contract::reply),
+                .with_reply(crate::contract::reply)
+                .with_migrate(crate::contract::migrate)
+                .with_ibc(
+                    crate::contract::ibc_channel_open,
+                    crate::contract::ibc_channel_connect,
+                    crate::contract::ibc_channel_close,
+                    crate::contract::ibc_packet_receive,
+                    crate::contract::ibc_packet_ack,
+                    crate::contract::ibc_packet_timeout,
+                ),
             )
         }
         fn wasm(_chain: &ChainInfoOwned) -> WasmPath {
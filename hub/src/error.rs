@@ -1,7 +1,9 @@
 use abstract_adapter::std::{objects::version_control::VersionControlError, AbstractError};
 use abstract_adapter::AdapterError;
 use abstract_sdk::AbstractSdkError;
-use cosmwasm_std::{Instantiate2AddressError, StdError};
+use cosmwasm_std::{Decimal, Instantiate2AddressError, StdError};
+
+use crate::msg::MetadataField;
 use cw_asset::AssetError;
 use cw_controllers::AdminError;
 use thiserror::Error;
@@ -32,6 +34,9 @@ pub enum HubError {
     #[error("{0}")]
     Instantiate2Address(#[from] Instantiate2AddressError),
 
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -40,4 +45,129 @@ pub enum HubError {
 
     #[error("Ibc Transfer failed {0}")]
     Transfer(String),
+
+    #[error("Ibc Transfer needs at least one token")]
+    EmptyTransfer {},
+
+    #[error("Ibc Transfer needs at least one destination chain")]
+    EmptyRoute {},
+
+    #[error("No forwarded transfer found for token {0}")]
+    UnknownTransfer(String),
+
+    #[error("ICS-721 channel {0} doesn't exist")]
+    UnknownChannel(String),
+
+    #[error("Invalid ICS-721 channel: {0}")]
+    InvalidChannel(String),
+
+    #[error("ICS-721 channels can't be closed")]
+    ChannelClose {},
+
+    #[error("Unknown ICS-721 class {0}, only tokens of the hub collection can come back")]
+    UnknownClass(String),
+
+    #[error("Token {0} was not sent through this channel")]
+    NotEscrowed(String),
+
+    #[error("Token {0} is listed more than once in the packet")]
+    DuplicateToken(String),
+
+    #[error("Tokens already exist on this chain: {}", token_ids.join(","))]
+    TokenAlreadyExists { token_ids: Vec<String> },
+
+    #[error("Cross-chain transfers are paused")]
+    Paused {},
+
+    #[error("Cross-chain transfers with chain {0} are paused")]
+    ChainPaused(String),
+
+    #[error("Chain {0} is not allowed")]
+    ChainNotAllowed(String),
+
+    #[error("Account can't send more than {max_tokens} tokens every {window} seconds")]
+    AccountRateLimited { max_tokens: u32, window: u64 },
+
+    #[error("The rate limit window must last at least one second")]
+    EmptyRateLimitWindow {},
+
+    #[error("Chain {chain} can't receive more than {max_tokens} tokens every {window} seconds")]
+    ChainRateLimited {
+        chain: String,
+        max_tokens: u32,
+        window: u64,
+    },
+
+    #[error("Tokens sent by a plain address need a recipient")]
+    MissingRecipient {},
+
+    #[error("Transfers to chain {0} have a fee and can only be sent from an account")]
+    FeeRequired(String),
+
+    #[error("Only tokens of the hub collections can be received")]
+    UnknownCollection {},
+
+    #[error("Collection {0} doesn't exist on this chain")]
+    CollectionNotFound(String),
+
+    #[error("Collection {0} already exists")]
+    CollectionAlreadyExists(String),
+
+    #[error("Tokens of collection {0} are soulbound and can't leave their account")]
+    Soulbound(String),
+
+    #[error("Can't migrate {contract} from version {from} to {to}")]
+    CannotMigrate {
+        contract: String,
+        from: String,
+        to: String,
+    },
+
+    #[error("The admin account is required to migrate from version {0}")]
+    MissingAdminAccount(String),
+
+    #[error("The NFT code id is required to migrate from version {0}")]
+    MissingNftCodeId(String),
+
+    #[error("No admin account was proposed")]
+    NoPendingAdmin {},
+
+    #[error("Royalty share {0} is above 1")]
+    InvalidRoyalty(Decimal),
+
+    #[error("The hub needs to be the minter of NFT contract {nft}, its minter is {minter}")]
+    NotNftMinter { nft: String, minter: String },
+
+    #[error("The metadata patch doesn't change any field")]
+    EmptyMetadataPatch {},
+
+    #[error("No token to claim")]
+    NothingToClaim {},
+
+    #[error("No undelivered route result for chain {0}")]
+    NoUndeliveredRouteResults(String),
+
+    #[error("No token of the 0.0.1 collection is left to backfill")]
+    NoLegacyBackfill {},
+
+    #[error("Cross-chain transfers can't resume before the tokens of the 0.0.1 collection are backfilled")]
+    LegacyBackfillPending {},
+
+    #[error("Module {0} is not installed on the recipient account")]
+    ModuleNotInstalled(String),
+
+    #[error("Module {0} is not an app and can't be called on arrival")]
+    NotAHookModule(String),
+
+    #[error("Module {module_id} is not allowed to change the {field:?} metadata field")]
+    MetadataFieldNotAllowed {
+        module_id: String,
+        field: MetadataField,
+    },
+}
+
+impl From<semver::Error> for HubError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
 }
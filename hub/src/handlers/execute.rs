@@ -1,18 +1,36 @@
 use crate::contract::{Hub, HubResult};
 use crate::error::HubError;
-use crate::helpers::next_token_id_mut;
-use crate::ibc::TRANSFER_CALLBACK;
-use crate::msg::{HubExecuteMsg, HubIbcCallbackMsg, HubIbcMsg};
-use crate::state::NFT;
-use abstract_adapter::std::ibc::CallbackInfo;
-use abstract_adapter::std::ibc_client::InstalledModuleIdentification;
-use abstract_adapter::std::objects::module::ModuleInfo;
-use abstract_adapter::std::{ibc_client, IBC_CLIENT};
-use abstract_sdk::features::{AccountIdentification, ModuleIdentification};
-use abstract_sdk::{AbstractResponse, AccountAction, Execution, ModuleInterface};
-use common::NAMESPACE;
-use cosmwasm_std::{ensure_eq, to_json_binary, wasm_execute, DepsMut, Env, MessageInfo};
-use cw721::{NftInfoResponse, OwnerOfResponse};
+use crate::handlers::migrate::{legacy_token_owners, record_legacy_tokens, TOKENS_PAGE_LIMIT};
+use crate::helpers::{
+    collection_address, collection_id_of, collection_or_default, ensure_admin, ensure_chain_open,
+    ibc_client_address, index_token, is_trusted_namespace, load_collection, new_collection,
+    next_token_id_mut, royalty_msg, save_collection, token_owner, transferable_collection,
+    unindex_token, update_metadata_msg, update_supply,
+};
+use crate::ibc::ics721;
+use crate::ibc::transfer::{route_result_msg, send_tokens, OutboundTransfer};
+use crate::msg::{
+    ArrivalHook, HubExecuteMsg, HubReceiveMsg, IbcNft, MetadataField, MetadataPatch, RateLimits,
+    Recipient, Royalty, TransferMode,
+};
+use crate::rate_limit::consume_quota;
+use crate::state::{
+    transfer_origin, Collection, CHAIN_SETTINGS, COLLECTIONS, CONFIG, LEGACY_BACKFILL,
+    METADATA_PERMISSIONS, PAUSED, PENDING_ADMIN, PENDING_CLAIMS, RATE_LIMITS, ROYALTIES,
+    TRANSFER_FEES, TRUSTED_NAMESPACES, UNDELIVERED_ROUTE_RESULTS,
+};
+use abstract_adapter::std::objects::{module::ModuleInfo, AccountId};
+use abstract_adapter::std::IBC_CLIENT;
+use abstract_sdk::features::AccountIdentification;
+use abstract_sdk::{
+    AbstractResponse, AccountAction, AccountVerification, Execution, ExecutorMsg, ModuleInterface,
+    TransferInterface,
+};
+use cosmwasm_std::{
+    ensure_eq, from_json, wasm_execute, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut,
+    Empty, Env, MessageInfo, Order, StdResult, WasmMsg,
+};
+use cw721::{Cw721ReceiveMsg, NftInfoResponse, OwnerOfResponse};
 use cw721_metadata_onchain::{ExecuteMsg, Metadata};
 use cw721_metadata_onchain::{Extension, QueryMsg};
 
@@ -27,118 +45,924 @@ pub fn execute_handler(
         HubExecuteMsg::IbcTransfer {
             recipient_chain,
             token_id,
-        } => ibc_transfer(deps, info, env, adapter, token_id, recipient_chain),
+            collection_id,
+            recipient,
+            fallback_address,
+            route,
+            on_arrival,
+        } => ibc_transfer(
+            deps,
+            info,
+            env,
+            adapter,
+            collection_or_default(collection_id),
+            vec![token_id],
+            recipient_chain,
+            recipient,
+            fallback_address,
+            route,
+            on_arrival,
+        ),
+        HubExecuteMsg::IbcTransferBatch {
+            recipient_chain,
+            token_ids,
+            collection_id,
+            recipient,
+            fallback_address,
+            route,
+            on_arrival,
+        } => ibc_transfer(
+            deps,
+            info,
+            env,
+            adapter,
+            collection_or_default(collection_id),
+            token_ids,
+            recipient_chain,
+            recipient,
+            fallback_address,
+            route,
+            on_arrival,
+        ),
+        HubExecuteMsg::Ics721Transfer {
+            token_ids,
+            collection_id,
+            channel_id,
+            receiver,
+            memo,
+        } => ics721_transfer(
+            deps,
+            env,
+            adapter,
+            collection_or_default(collection_id),
+            token_ids,
+            channel_id,
+            receiver,
+            memo,
+        ),
         HubExecuteMsg::Mint {
             module_id,
+            collection_id,
+            token_uri,
+            metadata,
+            royalty,
+        } => mint(
+            deps,
+            info,
+            env,
+            module_id,
+            collection_or_default(collection_id),
             token_uri,
             metadata,
-        } => mint(deps, info, env, module_id, token_uri, metadata, adapter),
-        HubExecuteMsg::ModifyMetadata {} => todo!(),
+            royalty,
+            adapter,
+        ),
+        HubExecuteMsg::ClaimPending {} => claim_pending(deps, adapter),
+        HubExecuteMsg::SetTransferFee { chain, fee } => set_transfer_fee(deps, adapter, chain, fee),
+        HubExecuteMsg::UpdateChainSettings {
+            chain,
+            allowlisted,
+            denylisted,
+            paused,
+        } => update_chain_settings(deps, adapter, chain, allowlisted, denylisted, paused),
+        HubExecuteMsg::SetPaused { paused } => set_paused(deps, adapter, paused),
+        HubExecuteMsg::SetRateLimits { rate_limits } => set_rate_limits(deps, adapter, rate_limits),
+        HubExecuteMsg::ModifyMetadata {
+            module_id,
+            token_id,
+            collection_id,
+            metadata,
+        } => modify_metadata(
+            deps,
+            info,
+            adapter,
+            module_id,
+            collection_or_default(collection_id),
+            token_id,
+            metadata,
+        ),
+        HubExecuteMsg::Burn {
+            module_id,
+            token_id,
+            collection_id,
+        } => burn(
+            deps,
+            info,
+            adapter,
+            module_id,
+            collection_or_default(collection_id),
+            token_id,
+        ),
+        HubExecuteMsg::SetMetadataPermissions { module_id, fields } => {
+            set_metadata_permissions(deps, adapter, module_id, fields)
+        }
+        HubExecuteMsg::CreateCollection {
+            collection_id,
+            name,
+            symbol,
+            soulbound,
+        } => create_collection(deps, env, adapter, collection_id, name, symbol, soulbound),
+        HubExecuteMsg::UpdateConfig {
+            nft_code_id,
+            transfer_mode,
+        } => update_config(deps, adapter, nft_code_id, transfer_mode),
+        HubExecuteMsg::UpdateTrustedNamespaces { add, remove } => {
+            update_trusted_namespaces(deps, adapter, add, remove)
+        }
+        HubExecuteMsg::UpdateNftAdmin {
+            collection_id,
+            admin,
+        } => update_nft_admin(deps, adapter, collection_id, admin),
+        HubExecuteMsg::MigrateNft {
+            collection_id,
+            code_id,
+            msg,
+        } => migrate_nft(deps, adapter, collection_id, code_id, msg),
+        HubExecuteMsg::ProposeAdmin { admin_account } => {
+            propose_admin(deps, adapter, admin_account)
+        }
+        HubExecuteMsg::AcceptAdmin {} => accept_admin(deps, adapter),
+        HubExecuteMsg::ResendRouteResults { chain } => resend_route_results(deps, adapter, chain),
+        HubExecuteMsg::BackfillLegacyTokens { limit } => {
+            backfill_legacy_tokens(deps, env, adapter, limit)
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn ibc_transfer(
-    deps: DepsMut,
+    mut deps: DepsMut,
     _info: MessageInfo,
     env: Env,
     hub: Hub,
-    token_id: String,
+    collection_id: String,
+    token_ids: Vec<String>,
     recipient_chain: String,
+    recipient: Option<Recipient>,
+    fallback_address: Option<String>,
+    route: Option<Vec<String>>,
+    on_arrival: Option<ArrivalHook>,
 ) -> HubResult {
-    let nft = NFT.load(deps.storage)?;
+    let nft = transferable_collection(deps.storage, &collection_id)?;
+    let (escrow_msg, tokens) =
+        escrow_tokens(deps.as_ref(), &env, &hub, &collection_id, &nft, token_ids)?;
+    for token in &tokens {
+        unindex_token(deps.storage, &collection_id, &token.token_id)?;
+    }
+    let account_id = hub.account_id(deps.as_ref())?;
 
-    // We authenticate the account that is calling the contract
-    let target_account = hub.account_base(deps.as_ref())?;
+    // The account pays the fee of the destination chain to the admin account
+    let fee_msg = transfer_fee(deps.as_ref(), &hub, &recipient_chain)?;
 
-    // We verify the NFT is owned by the addr
-    let owner: OwnerOfResponse = deps.querier.query_wasm_smart(
-        &nft,
-        &QueryMsg::OwnerOf {
-            token_id: token_id.clone(),
-            include_expired: None,
-        },
+    // The tokens go through the route chains before reaching the recipient chain
+    let mut path = route.unwrap_or_default();
+    path.push(recipient_chain);
+
+    // We will burn the tokens once the transfer has been confirmed and the callback has been received
+    // If the transfer fails, the tokens are given back to the account in the callback
+    let ibc_client_addr = hub.modules(deps.as_ref()).module_address(IBC_CLIENT)?;
+    let transfer = OutboundTransfer {
+        account_id: Some(account_id.clone()),
+        sender: hub.proxy_address(deps.as_ref())?,
+        indexed_account: Some(account_id),
+        collection_id,
+        recipient,
+        fallback_address,
+        tokens,
+        path,
+        previous_hop: None,
+        on_arrival,
+    };
+    let (ibc_msg, transfer_ids) =
+        start_transfer(deps.branch(), &env, &hub, ibc_client_addr, transfer)?;
+
+    Ok(hub
+        .custom_response("ibc-transfer", vec![("transfer_ids", transfer_ids)])
+        .add_message(escrow_msg)
+        .add_messages(fee_msg)
+        .add_message(ibc_msg))
+}
+
+/// Handles the tokens sent to the hub with cw721 `SendNft`
+/// This lets any holder, including plain addresses, send tokens cross-chain
+pub fn receive_nft_handler(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hub: Hub,
+    receive_msg: Cw721ReceiveMsg,
+) -> HubResult {
+    // Only tokens of the hub collections can be received, the NFT contract already transferred the token to the hub
+    let collection_id = collection_id_of(deps.storage, &info.sender)?;
+    let nft = transferable_collection(deps.storage, &collection_id)?;
+    let sender = deps.api.addr_validate(&receive_msg.sender)?;
+    // The sender can be the proxy of an account that sent the token with a cw721 transfer
+    // A token moved outside the hub is still indexed under its previous account, which isn't the sender then
+    let indexed_account = unindex_token(deps.storage, &collection_id, &receive_msg.token_id)?
+        .filter(|account_id| {
+            hub.account_registry(deps.as_ref())
+                .and_then(|registry| registry.account_base(account_id))
+                .is_ok_and(|base| base.proxy == sender)
+        });
+
+    match from_json(&receive_msg.msg)? {
+        HubReceiveMsg::IbcTransfer {
+            recipient_chain,
+            recipient,
+            fallback_address,
+            route,
+            on_arrival,
+        } => {
+            // The fee is paid through the account executor, plain addresses can't pay it in a cw721 hook
+            if TRANSFER_FEES.has(deps.storage, &recipient_chain) {
+                return Err(HubError::FeeRequired(recipient_chain));
+            }
+
+            let nft_info: NftInfoResponse<Extension> = deps.querier.query_wasm_smart(
+                &nft,
+                &QueryMsg::NftInfo {
+                    token_id: receive_msg.token_id.clone(),
+                },
+            )?;
+            let tokens = vec![IbcNft {
+                royalty: ROYALTIES
+                    .may_load(deps.storage, (&collection_id, &receive_msg.token_id))?,
+                token_id: receive_msg.token_id,
+                token_uri: nft_info.token_uri,
+                extension: nft_info.extension,
+            }];
+
+            let mut path = route.unwrap_or_default();
+            path.push(recipient_chain);
+
+            // The hub sends the tokens itself as there is no account to act on behalf of
+            let ibc_client_addr = ibc_client_address(deps.as_ref(), &hub)?;
+            let transfer = OutboundTransfer {
+                account_id: None,
+                sender,
+                indexed_account,
+                collection_id,
+                recipient: Some(recipient),
+                fallback_address,
+                tokens,
+                path,
+                previous_hop: None,
+                on_arrival,
+            };
+            let (ibc_msg, transfer_ids) =
+                start_transfer(deps.branch(), &env, &hub, ibc_client_addr, transfer)?;
+
+            Ok(hub
+                .custom_response("ibc-transfer", vec![("transfer_ids", transfer_ids)])
+                .add_message(ibc_msg))
+        }
+    }
+}
+
+/// Checks that the transfer is allowed and sends the escrowed tokens along their path
+/// Returns the IBC message and the ids of the registered transfers
+fn start_transfer(
+    mut deps: DepsMut,
+    env: &Env,
+    hub: &Hub,
+    ibc_client: Addr,
+    transfer: OutboundTransfer,
+) -> HubResult<(CosmosMsg, String)> {
+    for chain in &transfer.path {
+        ensure_chain_open(deps.as_ref(), chain)?;
+    }
+
+    let destination_chain = transfer.path.last().ok_or(HubError::EmptyRoute {})?;
+    consume_quota(
+        deps.branch(),
+        env,
+        &transfer_origin(transfer.account_id.as_ref(), &transfer.sender),
+        destination_chain,
+        transfer.tokens.len() as u32,
     )?;
-    if owner.owner != target_account.proxy_address(deps.as_ref())? {
-        return Err(HubError::Unauthorized {});
+
+    let (ibc_msg, transfer_ids) = send_tokens(deps, env, hub, ibc_client, transfer)?;
+
+    let transfer_ids = transfer_ids
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    Ok((ibc_msg, transfer_ids))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn ics721_transfer(
+    mut deps: DepsMut,
+    env: Env,
+    hub: Hub,
+    collection_id: String,
+    token_ids: Vec<String>,
+    channel_id: String,
+    receiver: String,
+    memo: Option<String>,
+) -> HubResult {
+    // The channel takes the place of the destination chain for the settings, fees and rate limits
+    ensure_chain_open(deps.as_ref(), &channel_id)?;
+    let nft = transferable_collection(deps.storage, &collection_id)?;
+    let (escrow_msg, tokens) =
+        escrow_tokens(deps.as_ref(), &env, &hub, &collection_id, &nft, token_ids)?;
+    for token in &tokens {
+        unindex_token(deps.storage, &collection_id, &token.token_id)?;
     }
+    let fee_msg = transfer_fee(deps.as_ref(), &hub, &channel_id)?;
+    let account_id = hub.account_id(deps.as_ref())?;
+    consume_quota(
+        deps.branch(),
+        &env,
+        &account_id.to_string(),
+        &channel_id,
+        tokens.len() as u32,
+    )?;
+
+    // The tokens stay escrowed in the hub until they come back through the same channel
+    let sender = hub.proxy_address(deps.as_ref())?;
+    let packet_msg = ics721::send_packet(
+        deps, &env, &nft, channel_id, sender, account_id, receiver, memo, tokens,
+    )?;
+
+    Ok(hub
+        .response("ics721-transfer")
+        .add_message(escrow_msg)
+        .add_messages(fee_msg)
+        .add_message(packet_msg))
+}
 
-    // We transfer the NFT from the top level owner to this contract to lock it
-    let nft_msg = hub
-        .executor(deps.as_ref())
-        .execute(vec![AccountAction::from_vec(vec![wasm_execute(
-            &nft,
+/// Gives the calling account the tokens the hub received for it before it existed on this chain
+fn claim_pending(deps: DepsMut, hub: Hub) -> HubResult {
+    let account_id = hub.account_id(deps.as_ref())?;
+    let proxy_address = hub.proxy_address(deps.as_ref())?;
+
+    let claims = PENDING_CLAIMS
+        .prefix(&account_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    if claims.is_empty() {
+        return Err(HubError::NothingToClaim {});
+    }
+
+    let mut msgs = vec![];
+    let mut token_ids = vec![];
+    for ((collection_id, token_id), _) in claims {
+        PENDING_CLAIMS.remove(deps.storage, (&account_id, &collection_id, &token_id));
+        index_token(deps.storage, &account_id, &collection_id, &token_id)?;
+        msgs.push(wasm_execute(
+            collection_address(deps.storage, &collection_id)?,
             &ExecuteMsg::TransferNft {
-                recipient: env.contract.address.to_string(),
+                recipient: proxy_address.to_string(),
                 token_id: token_id.clone(),
             },
             vec![],
-        )?])])?;
+        )?);
+        token_ids.push(token_id);
+    }
 
-    // We send an IBC mint message from to the distant chain
-    // We query the NFT metadata
-    let nft: NftInfoResponse<Extension> = deps.querier.query_wasm_smart(
-        NFT.load(deps.storage)?,
-        &QueryMsg::NftInfo {
-            token_id: token_id.clone(),
-        },
-    )?;
+    Ok(hub
+        .custom_response("claim-pending", vec![("token_ids", token_ids.join(","))])
+        .add_messages(msgs))
+}
 
-    let current_module_info = ModuleInfo::from_id(hub.module_id(), hub.version().into())?;
-    let ibc_msg = ibc_client::ExecuteMsg::ModuleIbcAction {
-        host_chain: recipient_chain,
-        target_module: current_module_info,
-        msg: to_json_binary(&HubIbcMsg::IbcMint {
-            token_id: token_id.clone(),
-            token_uri: nft.token_uri,
-            extension: nft.extension,
-            local_account_id: target_account.account_id(deps.as_ref())?,
-        })?,
-        callback_info: Some(CallbackInfo {
-            id: TRANSFER_CALLBACK.to_string(),
-            msg: Some(to_json_binary(&HubIbcCallbackMsg::BurnToken { token_id })?),
-        }),
+/// Creates the message that pays the fee to send tokens to `destination_chain`, if there is one
+fn transfer_fee(deps: Deps, hub: &Hub, destination_chain: &str) -> HubResult<Option<ExecutorMsg>> {
+    let Some(fee) = TRANSFER_FEES.may_load(deps.storage, destination_chain)? else {
+        return Ok(None);
     };
+    let admin_account = CONFIG.load(deps.storage)?.admin_account;
+    let admin_proxy = hub
+        .account_registry(deps)?
+        .account_base(&admin_account)?
+        .proxy;
 
-    let ibc_client_addr = hub.modules(deps.as_ref()).module_address(IBC_CLIENT)?;
+    let payment_msg = hub.bank(deps).transfer(vec![fee], &admin_proxy)?;
+    Ok(Some(hub.executor(deps).execute(vec![payment_msg])?))
+}
 
-    // We will burn the token once the transfer has been confirmed and the callback has been received
-    let ibc_msg = wasm_execute(ibc_client_addr, &ibc_msg, vec![])?;
+fn set_transfer_fee(deps: DepsMut, hub: Hub, chain: String, fee: Option<Coin>) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
 
-    Ok(hub
-        .response("ibc-transfer")
-        .add_message(nft_msg)
-        .add_message(ibc_msg))
+    match &fee {
+        Some(fee) => TRANSFER_FEES.save(deps.storage, &chain, fee)?,
+        None => TRANSFER_FEES.remove(deps.storage, &chain),
+    }
+
+    let fee = fee.map(|fee| fee.to_string()).unwrap_or_default();
+    Ok(hub.custom_response("set-transfer-fee", vec![("chain", chain), ("fee", fee)]))
+}
+
+fn update_chain_settings(
+    deps: DepsMut,
+    hub: Hub,
+    chain: String,
+    allowlisted: Option<bool>,
+    denylisted: Option<bool>,
+    paused: Option<bool>,
+) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+
+    let mut settings = CHAIN_SETTINGS
+        .may_load(deps.storage, &chain)?
+        .unwrap_or_default();
+    if let Some(allowlisted) = allowlisted {
+        settings.allowlisted = allowlisted;
+    }
+    if let Some(denylisted) = denylisted {
+        settings.denylisted = denylisted;
+    }
+    if let Some(paused) = paused {
+        settings.paused = paused;
+    }
+    CHAIN_SETTINGS.save(deps.storage, &chain, &settings)?;
+
+    Ok(hub.custom_response(
+        "update-chain-settings",
+        vec![
+            ("chain", chain),
+            ("allowlisted", settings.allowlisted.to_string()),
+            ("denylisted", settings.denylisted.to_string()),
+            ("paused", settings.paused.to_string()),
+        ],
+    ))
 }
 
+fn set_paused(deps: DepsMut, hub: Hub, paused: bool) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+    if !paused && LEGACY_BACKFILL.exists(deps.storage) {
+        return Err(HubError::LegacyBackfillPending {});
+    }
+
+    PAUSED.save(deps.storage, &paused)?;
+
+    Ok(hub.custom_response("set-paused", vec![("paused", paused.to_string())]))
+}
+
+fn set_rate_limits(deps: DepsMut, hub: Hub, rate_limits: Option<RateLimits>) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+
+    match &rate_limits {
+        Some(rate_limits) if rate_limits.window == 0 => {
+            return Err(HubError::EmptyRateLimitWindow {})
+        }
+        Some(rate_limits) => RATE_LIMITS.save(deps.storage, rate_limits)?,
+        None => RATE_LIMITS.remove(deps.storage),
+    }
+
+    Ok(hub.response("set-rate-limits"))
+}
+
+/// Creates the message that locks the tokens of the account in the hub and returns their data
+fn escrow_tokens(
+    deps: Deps,
+    env: &Env,
+    hub: &Hub,
+    collection_id: &str,
+    nft: &Addr,
+    token_ids: Vec<String>,
+) -> HubResult<(ExecutorMsg, Vec<IbcNft>)> {
+    if token_ids.is_empty() {
+        return Err(HubError::EmptyTransfer {});
+    }
+
+    // We authenticate the account that is calling the contract
+    let proxy_address = hub.proxy_address(deps)?;
+
+    let mut escrow_msgs = vec![];
+    let mut tokens = vec![];
+    for token_id in token_ids {
+        // We verify the NFT is owned by the addr
+        let owner: OwnerOfResponse = deps.querier.query_wasm_smart(
+            nft,
+            &QueryMsg::OwnerOf {
+                token_id: token_id.clone(),
+                include_expired: None,
+            },
+        )?;
+        if owner.owner != proxy_address {
+            return Err(HubError::Unauthorized {});
+        }
+
+        // We transfer the NFT from the top level owner to this contract to lock it
+        escrow_msgs.push(wasm_execute(
+            nft,
+            &ExecuteMsg::TransferNft {
+                recipient: env.contract.address.to_string(),
+                token_id: token_id.clone(),
+            },
+            vec![],
+        )?);
+
+        // We query the NFT metadata that will be sent to the distant chain
+        let nft_info: NftInfoResponse<Extension> = deps.querier.query_wasm_smart(
+            nft,
+            &QueryMsg::NftInfo {
+                token_id: token_id.clone(),
+            },
+        )?;
+        tokens.push(IbcNft {
+            royalty: ROYALTIES.may_load(deps.storage, (collection_id, &token_id))?,
+            token_id,
+            token_uri: nft_info.token_uri,
+            extension: nft_info.extension,
+        });
+    }
+
+    let escrow_msg = hub
+        .executor(deps)
+        .execute(vec![AccountAction::from_vec(escrow_msgs)])?;
+
+    Ok((escrow_msg, tokens))
+}
+
+#[allow(clippy::too_many_arguments)]
 fn mint(
     mut deps: DepsMut,
     info: MessageInfo,
     env: Env,
     module_id: String,
+    collection_id: String,
     token_uri: String,
     metadata: Metadata,
+    royalty: Option<Royalty>,
     adapter: Hub,
 ) -> HubResult {
-    // This endpoint is permissionned because we're the hub, only authorized installed modules can call this
-    let module_addr = adapter.modules(deps.as_ref()).module_address(&module_id)?;
-    ensure_eq!(module_addr, info.sender, HubError::Unauthorized {});
-    let namespace = ModuleInfo::from_id_latest(&module_id)?.namespace;
-    ensure_eq!(namespace.as_str(), NAMESPACE, HubError::WrongNamespace {});
+    ensure_namespace_module(deps.as_ref(), &info, &adapter, &module_id)?;
+    let collection = load_collection(deps.storage, &collection_id)?;
+    if let Some(royalty) = &royalty {
+        if royalty.share > Decimal::one() {
+            return Err(HubError::InvalidRoyalty(royalty.share));
+        }
+    }
 
+    let account_id = adapter.account_id(deps.as_ref())?;
     let account_base = adapter.account_base(deps.as_ref())?;
 
     // We mint the token to the recipient
     let token_id = next_token_id_mut(deps.branch(), env)?;
+    update_supply(deps.storage, |supply| supply.minted += 1)?;
+    index_token(deps.storage, &account_id, &collection_id, &token_id)?;
     let mint_msg = wasm_execute(
-        NFT.load(deps.storage)?,
+        &collection.nft,
         &ExecuteMsg::Mint(cw721_base::MintMsg {
-            token_id,
+            token_id: token_id.clone(),
             owner: account_base.proxy.to_string(),
             token_uri: Some(token_uri),
             extension: Some(metadata),
         }),
         vec![],
     )?;
+    // The royalty is set in the NFT contract once the token exists
+    let set_royalty_msg = match &royalty {
+        Some(royalty) => {
+            ROYALTIES.save(deps.storage, (&collection_id, &token_id), royalty)?;
+            royalty_msg(deps.as_ref(), &adapter, &collection.nft, &token_id, royalty)?
+        }
+        None => None,
+    };
+
+    Ok(adapter
+        .response("mint-lost-nft")
+        .add_message(mint_msg)
+        .add_messages(set_royalty_msg))
+}
+
+/// This endpoint is permissionned because we're the hub, only authorized installed modules can call this
+fn ensure_namespace_module(
+    deps: Deps,
+    info: &MessageInfo,
+    hub: &Hub,
+    module_id: &str,
+) -> HubResult<()> {
+    let module_addr = hub.modules(deps).module_address(module_id)?;
+    ensure_eq!(module_addr, info.sender, HubError::Unauthorized {});
+    let namespace = ModuleInfo::from_id_latest(module_id)?.namespace;
+    if !is_trusted_namespace(deps.storage, namespace.as_str())? {
+        return Err(HubError::WrongNamespace {});
+    }
+    Ok(())
+}
+
+fn modify_metadata(
+    deps: DepsMut,
+    info: MessageInfo,
+    hub: Hub,
+    module_id: String,
+    collection_id: String,
+    token_id: String,
+    patch: MetadataPatch,
+) -> HubResult {
+    ensure_namespace_module(deps.as_ref(), &info, &hub, &module_id)?;
+
+    let fields = patch.fields();
+    if fields.is_empty() {
+        return Err(HubError::EmptyMetadataPatch {});
+    }
+    // The module can only change the fields the admin allowed
+    let allowed_fields = METADATA_PERMISSIONS
+        .may_load(deps.storage, &module_id)?
+        .unwrap_or_default();
+    if let Some(field) = fields
+        .into_iter()
+        .find(|field| !allowed_fields.contains(field))
+    {
+        return Err(HubError::MetadataFieldNotAllowed { module_id, field });
+    }
+
+    // We verify the NFT is owned by the account
+    let nft = collection_address(deps.storage, &collection_id)?;
+    let proxy_address = hub.proxy_address(deps.as_ref())?;
+    if token_owner(deps.as_ref(), &nft, &token_id) != Some(proxy_address.to_string()) {
+        return Err(HubError::Unauthorized {});
+    }
+
+    let nft_info: NftInfoResponse<Extension> = deps.querier.query_wasm_smart(
+        &nft,
+        &QueryMsg::NftInfo {
+            token_id: token_id.clone(),
+        },
+    )?;
+    let mut metadata = nft_info.extension.unwrap_or_default();
+    patch.apply(&mut metadata);
+
+    let update_msg = update_metadata_msg(&nft, &token_id, nft_info.token_uri, Some(metadata))?;
+
+    Ok(hub
+        .custom_response(
+            "modify-metadata",
+            vec![("module_id", module_id), ("token_id", token_id)],
+        )
+        .add_message(update_msg))
+}
+
+fn burn(
+    deps: DepsMut,
+    info: MessageInfo,
+    hub: Hub,
+    module_id: String,
+    collection_id: String,
+    token_id: String,
+) -> HubResult {
+    ensure_namespace_module(deps.as_ref(), &info, &hub, &module_id)?;
+
+    // We verify the NFT is owned by the account
+    let collection = load_collection(deps.storage, &collection_id)?;
+    let proxy_address = hub.proxy_address(deps.as_ref())?;
+    if token_owner(deps.as_ref(), &collection.nft, &token_id) != Some(proxy_address.to_string()) {
+        return Err(HubError::Unauthorized {});
+    }
+
+    let burn_msg = burn_token_msg(deps.as_ref(), &hub, &collection, &token_id)?;
+    update_supply(deps.storage, |supply| supply.consumed += 1)?;
+    ROYALTIES.remove(deps.storage, (&collection_id, &token_id));
+    unindex_token(deps.storage, &collection_id, &token_id)?;
+
+    Ok(hub
+        .custom_response(
+            "burn",
+            vec![
+                ("module_id", module_id),
+                ("token_id", token_id),
+                ("owner", proxy_address.to_string()),
+            ],
+        )
+        .add_message(burn_msg))
+}
+
+/// Creates the message that burns a token owned by the account
+/// Tokens of transferable collections are burned by the account, their owner
+/// The owner of a soulbound token can't approve the hub, so the hub burns it as the minter of the collection
+fn burn_token_msg(
+    deps: Deps,
+    hub: &Hub,
+    collection: &Collection,
+    token_id: &str,
+) -> HubResult<CosmosMsg> {
+    let burn_msg = wasm_execute(
+        &collection.nft,
+        &ExecuteMsg::Burn {
+            token_id: token_id.to_string(),
+        },
+        vec![],
+    )?;
+    if collection.soulbound {
+        return Ok(burn_msg.into());
+    }
+    Ok(hub
+        .executor(deps)
+        .execute(vec![AccountAction::from_vec(vec![burn_msg])])?
+        .into())
+}
+
+fn set_metadata_permissions(
+    deps: DepsMut,
+    hub: Hub,
+    module_id: String,
+    fields: Vec<MetadataField>,
+) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+
+    if fields.is_empty() {
+        METADATA_PERMISSIONS.remove(deps.storage, &module_id);
+    } else {
+        METADATA_PERMISSIONS.save(deps.storage, &module_id, &fields)?;
+    }
+
+    Ok(hub.custom_response("set-metadata-permissions", vec![("module_id", module_id)]))
+}
+
+fn create_collection(
+    deps: DepsMut,
+    env: Env,
+    hub: Hub,
+    collection_id: String,
+    name: String,
+    symbol: String,
+    soulbound: bool,
+) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+    if COLLECTIONS.has(deps.storage, &collection_id) {
+        return Err(HubError::CollectionAlreadyExists(collection_id));
+    }
+
+    let (instantiate_msg, collection) =
+        new_collection(deps.as_ref(), &env, &collection_id, name, symbol, soulbound)?;
+    save_collection(deps.storage, &collection_id, &collection)?;
+
+    Ok(hub
+        .custom_response(
+            "create-collection",
+            vec![
+                ("collection_id", collection_id),
+                ("nft", collection.nft.to_string()),
+            ],
+        )
+        .add_message(instantiate_msg))
+}
+
+fn update_config(
+    deps: DepsMut,
+    hub: Hub,
+    nft_code_id: Option<u64>,
+    transfer_mode: Option<TransferMode>,
+) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(nft_code_id) = nft_code_id {
+        config.nft_code_id = nft_code_id;
+    }
+    if let Some(transfer_mode) = transfer_mode {
+        config.transfer_mode = transfer_mode;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(hub.response("update-config"))
+}
+
+fn update_trusted_namespaces(
+    deps: DepsMut,
+    hub: Hub,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+
+    for namespace in &add {
+        TRUSTED_NAMESPACES.save(deps.storage, namespace, &Empty {})?;
+    }
+    for namespace in &remove {
+        TRUSTED_NAMESPACES.remove(deps.storage, namespace);
+    }
+
+    Ok(hub.custom_response(
+        "update-trusted-namespaces",
+        vec![("added", add.join(",")), ("removed", remove.join(","))],
+    ))
+}
+
+fn update_nft_admin(
+    deps: DepsMut,
+    hub: Hub,
+    collection_id: String,
+    admin: Option<String>,
+) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+
+    let contract_addr = collection_address(deps.storage, &collection_id)?.to_string();
+    let admin_msg = match &admin {
+        Some(admin) => WasmMsg::UpdateAdmin {
+            contract_addr,
+            admin: deps.api.addr_validate(admin)?.to_string(),
+        },
+        None => WasmMsg::ClearAdmin { contract_addr },
+    };
+
+    Ok(hub
+        .custom_response(
+            "update-nft-admin",
+            vec![
+                ("collection_id", collection_id),
+                ("admin", admin.unwrap_or_default()),
+            ],
+        )
+        .add_message(admin_msg))
+}
+
+fn migrate_nft(
+    deps: DepsMut,
+    hub: Hub,
+    collection_id: String,
+    code_id: u64,
+    msg: Binary,
+) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+
+    let migrate_msg = WasmMsg::Migrate {
+        contract_addr: collection_address(deps.storage, &collection_id)?.to_string(),
+        new_code_id: code_id,
+        msg,
+    };
+
+    Ok(hub
+        .custom_response(
+            "migrate-nft",
+            vec![
+                ("collection_id", collection_id),
+                ("code_id", code_id.to_string()),
+            ],
+        )
+        .add_message(migrate_msg))
+}
+
+/// The admin role is transferred in two steps, so that it can't be given to an account that can't use it
+fn propose_admin(deps: DepsMut, hub: Hub, admin_account: AccountId) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+
+    PENDING_ADMIN.save(deps.storage, &admin_account)?;
+
+    Ok(hub.custom_response(
+        "propose-admin",
+        vec![("admin_account", admin_account.to_string())],
+    ))
+}
+
+fn accept_admin(deps: DepsMut, hub: Hub) -> HubResult {
+    let pending_admin = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(HubError::NoPendingAdmin {})?;
+    if hub.account_id(deps.as_ref())? != pending_admin {
+        return Err(HubError::Unauthorized {});
+    }
+
+    PENDING_ADMIN.remove(deps.storage);
+    CONFIG.update(deps.storage, |mut config| -> HubResult<_> {
+        config.admin_account = pending_admin.clone();
+        Ok(config)
+    })?;
+
+    Ok(hub.custom_response(
+        "accept-admin",
+        vec![("admin_account", pending_admin.to_string())],
+    ))
+}
+
+fn resend_route_results(deps: DepsMut, hub: Hub, chain: String) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+
+    let route_results = UNDELIVERED_ROUTE_RESULTS
+        .may_load(deps.storage, &chain)?
+        .ok_or_else(|| HubError::NoUndeliveredRouteResults(chain.clone()))?;
+    UNDELIVERED_ROUTE_RESULTS.remove(deps.storage, &chain);
+
+    let msgs = route_results
+        .into_iter()
+        .map(|route_result| route_result_msg(deps.as_ref(), &hub, chain.clone(), route_result))
+        .collect::<HubResult<Vec<_>>>()?;
+
+    Ok(hub
+        .custom_response("resend-route-results", vec![("chain", chain)])
+        .add_messages(msgs))
+}
+
+fn backfill_legacy_tokens(deps: DepsMut, env: Env, hub: Hub, limit: Option<u32>) -> HubResult {
+    ensure_admin(deps.as_ref(), &hub)?;
+
+    let limit = limit.unwrap_or(TOKENS_PAGE_LIMIT).min(TOKENS_PAGE_LIMIT);
+    let owners = legacy_token_owners(deps.as_ref(), limit)?;
+    // Tokens held by plain addresses aren't indexed
+    let registry = hub.account_registry(deps.as_ref())?;
+    let tokens = owners
+        .into_iter()
+        .map(|(token_id, owner)| (token_id, registry.account_id(&owner).ok()))
+        .collect::<Vec<_>>();
+    let walked = tokens.len();
+    let done = record_legacy_tokens(deps.storage, &env, tokens, limit)?;
 
-    Ok(adapter.response("mint-lost-nft").add_message(mint_msg))
+    Ok(hub.custom_response(
+        "backfill-legacy-tokens",
+        vec![("tokens", walked.to_string()), ("done", done.to_string())],
+    ))
 }
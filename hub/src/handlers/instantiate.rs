@@ -1,11 +1,12 @@
 use crate::contract::{Hub, HubResult};
+use crate::error::HubError;
+use crate::helpers::{instantiate_collection, save_collection};
 use crate::msg::HubInstantiateMsg;
-use crate::state::{Config, CONFIG, NFT};
-use cosmwasm_std::{
-    instantiate2_address, to_json_binary, Binary, CodeInfoResponse, DepsMut, Env, MessageInfo,
-    QueryRequest, Response, WasmMsg, WasmQuery,
-};
-use cw721_base::InstantiateMsg;
+use crate::state::{Collection, Config, CONFIG, DEFAULT_COLLECTION};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
+use cw721::ContractInfoResponse;
+use cw721_base::MinterResponse;
+use cw721_metadata_onchain::QueryMsg;
 
 pub fn instantiate_handler(
     deps: DepsMut,
@@ -14,36 +15,62 @@ pub fn instantiate_handler(
     _hub: Hub,
     msg: HubInstantiateMsg,
 ) -> HubResult {
-    let config: Config = Config { next_token_id: 0 };
+    let config: Config = Config {
+        admin_account: msg.admin_account,
+        nft_code_id: msg.nft_code_id,
+        next_token_id: 0,
+        transfer_mode: msg.transfer_mode.unwrap_or_default(),
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    // An existing NFT contract is used as is
+    if let Some(nft_address) = msg.nft_address {
+        let collection = adopt_collection(deps.as_ref(), &env, nft_address, msg.nft_label)?;
+        save_collection(deps.storage, DEFAULT_COLLECTION, &collection)?;
+        return Ok(Response::new().add_attribute("nft", collection.nft));
+    }
 
     // We need to create the NFT contract that will host everything locally
-    let salt = b"nft_contract".to_vec();
-    let nft_instantiation_msg = WasmMsg::Instantiate2 {
-        admin: Some(env.contract.address.to_string()),
-        code_id: msg.nft_code_id,
-        label: "Cosmos Adventures NFT".to_string(),
-        msg: to_json_binary(&InstantiateMsg {
-            name: "Cosmos Adventurers".to_string(),
-            symbol: "IBC-CA".to_string(),
-            minter: env.contract.address.to_string(),
-        })?,
-        funds: vec![],
-        salt: Binary(salt.clone()),
-    };
-    let code_id_info: CodeInfoResponse =
-        deps.querier
-            .query(&QueryRequest::Wasm(WasmQuery::CodeInfo {
-                code_id: msg.nft_code_id,
-            }))?;
-    let canon_nft = instantiate2_address(
-        code_id_info.checksum.as_slice(),
-        &deps.api.addr_canonicalize(env.contract.address.as_str())?,
-        &salt,
+    let (nft_instantiation_msg, collection) = instantiate_collection(
+        deps.as_ref(),
+        &env,
+        msg.nft_code_id,
+        b"nft_contract",
+        msg.nft_label,
+        msg.nft_name,
+        msg.nft_symbol,
+        false,
     )?;
-    NFT.save(deps.storage, &deps.api.addr_humanize(&canon_nft)?)?;
-
-    CONFIG.save(deps.storage, &config)?;
+    save_collection(deps.storage, DEFAULT_COLLECTION, &collection)?;
 
-    // Example instantiation that doesn't do anything
     Ok(Response::new().add_message(nft_instantiation_msg))
 }
+
+/// Checks that the hub can mint on an already deployed NFT contract and returns its collection
+fn adopt_collection(
+    deps: Deps,
+    env: &Env,
+    nft_address: String,
+    label: String,
+) -> HubResult<Collection> {
+    let nft = deps.api.addr_validate(&nft_address)?;
+
+    let minter: MinterResponse = deps.querier.query_wasm_smart(&nft, &QueryMsg::Minter {})?;
+    if minter.minter != env.contract.address {
+        return Err(HubError::NotNftMinter {
+            nft: nft.to_string(),
+            minter: minter.minter,
+        });
+    }
+
+    let contract_info: ContractInfoResponse = deps
+        .querier
+        .query_wasm_smart(&nft, &QueryMsg::ContractInfo {})?;
+    Ok(Collection {
+        nft,
+        name: contract_info.name,
+        symbol: contract_info.symbol,
+        label,
+        soulbound: false,
+    })
+}
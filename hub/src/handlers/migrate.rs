@@ -0,0 +1,394 @@
+use crate::contract::{Hub, HubResult, HUB_ID, HUB_VERSION};
+use crate::error::HubError;
+use crate::helpers::{collection_address, index_token, save_collection, update_supply};
+use crate::msg::{HubMigrateMsg, NftMigration, TransferMode};
+use crate::state::{
+    Collection, Config, LegacyBackfill, Supply, CONFIG, DEFAULT_COLLECTION, LEGACY_BACKFILL,
+    PAUSED, SUPPLY,
+};
+use abstract_adapter::std::objects::{chain_name::ChainName, AccountId};
+use abstract_sdk::AbstractResponse;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, StdResult, Storage, WasmMsg};
+use cw721::{OwnerOfResponse, TokensResponse};
+use cw721_metadata_onchain::QueryMsg;
+use cw_storage_plus::Item;
+use semver::Version;
+
+/// Config stored by the hub 0.0.1
+#[cw_serde]
+struct ConfigV0_0_1 {
+    next_token_id: u64,
+}
+
+const CONFIG_V0_0_1: Item<ConfigV0_0_1> = Item::new("config");
+/// The hub 0.0.1 had a single collection
+const NFT_V0_0_1: Item<Addr> = Item::new("nft");
+/// Largest page of tokens returned by the cw721 contracts
+pub const TOKENS_PAGE_LIMIT: u32 = 100;
+
+pub fn migrate_handler(mut deps: DepsMut, _env: Env, hub: Hub, msg: HubMigrateMsg) -> HubResult {
+    let stored = cw2::get_contract_version(deps.storage)?;
+    let from: Version = stored.version.parse()?;
+    let to: Version = HUB_VERSION.parse()?;
+    if stored.contract != HUB_ID || from >= to {
+        return Err(HubError::CannotMigrate {
+            contract: stored.contract,
+            from: stored.version,
+            to: HUB_VERSION.to_string(),
+        });
+    }
+
+    // Each step upgrades the state to the next version
+    if from < Version::new(0, 1, 0) {
+        migrate_from_v0_0_1(deps.branch(), msg.admin_account, msg.nft_code_id)?;
+    }
+
+    cw2::set_contract_version(deps.storage, HUB_ID, HUB_VERSION)?;
+
+    let nft_msgs = msg
+        .nft_migrations
+        .into_iter()
+        .map(|migration| nft_migration_msg(deps.storage, migration))
+        .collect::<HubResult<Vec<_>>>()?;
+
+    Ok(hub
+        .custom_response(
+            "migrate",
+            vec![("from", stored.version), ("to", HUB_VERSION.to_string())],
+        )
+        .add_messages(nft_msgs))
+}
+
+/// The 0.0.1 config only held the next token id and its single collection was stored on its own
+/// 0.0.1 didn't keep the inventory of the accounts nor the supply, they are backfilled by walking through its tokens with `BackfillLegacyTokens`
+/// Cross-chain transfers are paused until then, so that no token enters or leaves the collection during the walk
+fn migrate_from_v0_0_1(
+    deps: DepsMut,
+    admin_account: Option<AccountId>,
+    nft_code_id: Option<u64>,
+) -> HubResult<()> {
+    let admin_account = admin_account.ok_or(HubError::MissingAdminAccount("0.0.1".to_string()))?;
+    let nft_code_id = nft_code_id.ok_or(HubError::MissingNftCodeId("0.0.1".to_string()))?;
+    let legacy_config = CONFIG_V0_0_1.load(deps.storage)?;
+    let nft = NFT_V0_0_1.load(deps.storage)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            admin_account,
+            nft_code_id,
+            next_token_id: legacy_config.next_token_id,
+            transfer_mode: TransferMode::BurnAndMint,
+        },
+    )?;
+    // Every token id handed out by the 0.0.1 hub was minted on this chain
+    // They are all counted as burned until the walk finds them in the collection
+    SUPPLY.save(
+        deps.storage,
+        &Supply {
+            minted: legacy_config.next_token_id,
+            burned: legacy_config.next_token_id,
+            ..Default::default()
+        },
+    )?;
+
+    // The 0.0.1 collection was always instantiated with these settings
+    save_collection(
+        deps.storage,
+        DEFAULT_COLLECTION,
+        &Collection {
+            nft,
+            name: "Cosmos Adventurers".to_string(),
+            symbol: "IBC-CA".to_string(),
+            label: "Cosmos Adventures NFT".to_string(),
+            soulbound: false,
+        },
+    )?;
+    NFT_V0_0_1.remove(deps.storage);
+
+    LEGACY_BACKFILL.save(
+        deps.storage,
+        &LegacyBackfill {
+            start_after: None,
+            legacy_next_token_id: legacy_config.next_token_id,
+        },
+    )?;
+    PAUSED.save(deps.storage, &true)?;
+
+    Ok(())
+}
+
+/// Returns the next page of tokens of the 0.0.1 collection, with their owner
+pub fn legacy_token_owners(deps: Deps, limit: u32) -> HubResult<Vec<(String, Addr)>> {
+    let backfill = LEGACY_BACKFILL
+        .may_load(deps.storage)?
+        .ok_or(HubError::NoLegacyBackfill {})?;
+    let nft = collection_address(deps.storage, DEFAULT_COLLECTION)?;
+
+    let page: TokensResponse = deps.querier.query_wasm_smart(
+        &nft,
+        &QueryMsg::AllTokens {
+            start_after: backfill.start_after,
+            limit: Some(limit),
+        },
+    )?;
+    page.tokens
+        .into_iter()
+        .map(|token_id| {
+            let owner: OwnerOfResponse = deps.querier.query_wasm_smart(
+                &nft,
+                &QueryMsg::OwnerOf {
+                    token_id: token_id.clone(),
+                    include_expired: None,
+                },
+            )?;
+            Ok((token_id, Addr::unchecked(owner.owner)))
+        })
+        .collect()
+}
+
+/// Records a page of tokens of the 0.0.1 collection, with the account holding each of them if any
+/// Tokens of other chains were received, the missing tokens of this chain were burned when they left it
+/// Returns whether every token was walked through
+pub fn record_legacy_tokens(
+    storage: &mut dyn Storage,
+    env: &Env,
+    tokens: Vec<(String, Option<AccountId>)>,
+    limit: u32,
+) -> HubResult<bool> {
+    let mut backfill = LEGACY_BACKFILL
+        .may_load(storage)?
+        .ok_or(HubError::NoLegacyBackfill {})?;
+    let chain_name = ChainName::from_chain_id(&env.block.chain_id);
+    let done = tokens.len() < limit as usize;
+
+    for (token_id, account_id) in tokens {
+        if let Some(account_id) = &account_id {
+            index_token(storage, account_id, DEFAULT_COLLECTION, &token_id)?;
+        }
+        match token_id.split_once('>') {
+            Some((home_chain, _)) if home_chain != chain_name.as_str() => {
+                update_supply(storage, |supply| supply.received += 1)?
+            }
+            // Tokens minted after the migration aren't counted as burned
+            Some((_, id))
+                if id
+                    .parse()
+                    .is_ok_and(|id: u64| id < backfill.legacy_next_token_id) =>
+            {
+                update_supply(storage, |supply| supply.burned -= 1)?
+            }
+            _ => {}
+        }
+        backfill.start_after = Some(token_id);
+    }
+
+    if done {
+        LEGACY_BACKFILL.remove(storage);
+    } else {
+        LEGACY_BACKFILL.save(storage, &backfill)?;
+    }
+    Ok(done)
+}
+
+/// Whether the walk through the 0.0.1 collection already went past the token, or is over
+/// Tokens the walk hasn't reached yet are counted as burned if they are missing from the collection
+pub fn legacy_token_walked(storage: &dyn Storage, token_id: &str) -> StdResult<bool> {
+    Ok(LEGACY_BACKFILL.may_load(storage)?.map_or(true, |backfill| {
+        backfill
+            .start_after
+            .is_some_and(|last_token| token_id <= last_token.as_str())
+    }))
+}
+
+/// The hub is the admin of the NFT contracts it instantiated, so it can migrate them
+fn nft_migration_msg(storage: &dyn Storage, migration: NftMigration) -> HubResult<WasmMsg> {
+    Ok(WasmMsg::Migrate {
+        contract_addr: collection_address(storage, &migration.collection_id)?.to_string(),
+        new_code_id: migration.code_id,
+        msg: migration.msg,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::migrate;
+    use crate::helpers::collection_id_of;
+    use crate::state::{account_tokens, COLLECTIONS};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{from_json, to_json_binary, ContractResult, Order, SystemResult, WasmQuery};
+
+    #[test]
+    fn migrates_from_v0_0_1() {
+        let mut deps = mock_dependencies();
+        cw2::set_contract_version(deps.as_mut().storage, HUB_ID, "0.0.1").unwrap();
+        CONFIG_V0_0_1
+            .save(deps.as_mut().storage, &ConfigV0_0_1 { next_token_id: 7 })
+            .unwrap();
+        let nft = Addr::unchecked("nft");
+        NFT_V0_0_1.save(deps.as_mut().storage, &nft).unwrap();
+        // juno>1, juno>4 and juno>6 left the chain, juno>3 is still in flight and phoenix>0 was received
+        // juno>7 was minted by a module once migrated, it doesn't make up for a burned token
+        deps.querier.update_wasm(|query| {
+            let WasmQuery::Smart { msg, .. } = query else {
+                panic!("unexpected query {query:?}");
+            };
+            let owners = [
+                ("juno>0", "proxy"),
+                ("juno>2", "wallet"),
+                ("juno>3", "hub"),
+                ("juno>5", "wallet"),
+                ("juno>7", "wallet"),
+                ("phoenix>0", "proxy"),
+            ];
+            let response = match from_json(msg).unwrap() {
+                QueryMsg::AllTokens { start_after, limit } => to_json_binary(&TokensResponse {
+                    tokens: owners
+                        .iter()
+                        .map(|(token_id, _)| token_id.to_string())
+                        .filter(|token_id| {
+                            start_after.as_ref().map_or(true, |last| token_id > last)
+                        })
+                        .take(limit.unwrap() as usize)
+                        .collect(),
+                }),
+                QueryMsg::OwnerOf { token_id, .. } => to_json_binary(&OwnerOfResponse {
+                    owner: owners
+                        .iter()
+                        .find(|(id, _)| *id == token_id)
+                        .unwrap()
+                        .1
+                        .to_string(),
+                    approvals: vec![],
+                }),
+                _ => panic!("unexpected query"),
+            };
+            SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+        });
+
+        // The 0.0.1 state doesn't hold the admin account
+        let err = migrate(
+            deps.as_mut(),
+            mock_env(),
+            HubMigrateMsg {
+                admin_account: None,
+                nft_code_id: Some(2),
+                nft_migrations: vec![],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, HubError::MissingAdminAccount("0.0.1".to_string()));
+
+        let admin_account = AccountId::local(1);
+        migrate(
+            deps.as_mut(),
+            mock_env(),
+            HubMigrateMsg {
+                admin_account: Some(admin_account.clone()),
+                nft_code_id: Some(2),
+                nft_migrations: vec![],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            CONFIG.load(&deps.storage).unwrap(),
+            Config {
+                admin_account,
+                nft_code_id: 2,
+                next_token_id: 7,
+                transfer_mode: TransferMode::BurnAndMint,
+            }
+        );
+        assert_eq!(
+            COLLECTIONS.load(&deps.storage, DEFAULT_COLLECTION).unwrap(),
+            Collection {
+                nft,
+                name: "Cosmos Adventurers".to_string(),
+                symbol: "IBC-CA".to_string(),
+                label: "Cosmos Adventures NFT".to_string(),
+                soulbound: false,
+            }
+        );
+        assert_eq!(
+            collection_id_of(&deps.storage, &nft).unwrap(),
+            DEFAULT_COLLECTION
+        );
+        assert!(!NFT_V0_0_1.exists(&deps.storage));
+        assert_eq!(
+            cw2::get_contract_version(&deps.storage).unwrap().version,
+            HUB_VERSION
+        );
+        // Transfers stay paused until the tokens are backfilled
+        assert!(PAUSED.load(&deps.storage).unwrap());
+
+        // The owners are resolved to accounts by the execute handler
+        let mut env = mock_env();
+        env.block.chain_id = "juno-1".to_string();
+        let mut pages = 0;
+        loop {
+            pages += 1;
+            let tokens = legacy_token_owners(deps.as_ref(), 2)
+                .unwrap()
+                .into_iter()
+                .map(|(token_id, owner)| {
+                    (token_id, (owner == "proxy").then(|| AccountId::local(2)))
+                })
+                .collect();
+            if record_legacy_tokens(deps.as_mut().storage, &env, tokens, 2).unwrap() {
+                break;
+            }
+            // The in flight token is only counted once its callback settles after the walk went past it
+            assert_eq!(
+                legacy_token_walked(&deps.storage, "juno>3").unwrap(),
+                pages >= 2
+            );
+        }
+        assert_eq!(pages, 4);
+        assert!(!LEGACY_BACKFILL.exists(&deps.storage));
+        assert!(legacy_token_walked(&deps.storage, "juno>3").unwrap());
+        assert_eq!(
+            legacy_token_owners(deps.as_ref(), 2).unwrap_err(),
+            HubError::NoLegacyBackfill {}
+        );
+
+        let inventory = account_tokens()
+            .idx
+            .account
+            .prefix(AccountId::local(2).to_string())
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            inventory,
+            vec![
+                (DEFAULT_COLLECTION.to_string(), "juno>0".to_string()),
+                (DEFAULT_COLLECTION.to_string(), "phoenix>0".to_string()),
+            ]
+        );
+        assert_eq!(
+            SUPPLY.load(&deps.storage).unwrap(),
+            Supply {
+                minted: 7,
+                burned: 3,
+                received: 1,
+                ..Default::default()
+            }
+        );
+
+        // The hub can't be migrated again to the same version
+        let err = migrate(
+            deps.as_mut(),
+            mock_env(),
+            HubMigrateMsg {
+                admin_account: None,
+                nft_code_id: None,
+                nft_migrations: vec![],
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, HubError::CannotMigrate { .. }));
+    }
+}
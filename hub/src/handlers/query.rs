@@ -1,22 +1,124 @@
 use crate::contract::{Hub, HubResult};
-use crate::helpers::next_token_id;
-use crate::msg::{ConfigResponse, HubQueryMsg, NextTokenIdResponse};
-use crate::state::{CONFIG, NFT};
-use cosmwasm_std::{to_json_binary, Binary, Deps, Env};
+use crate::helpers::{
+    collection_address, collection_or_default, ensure_chain_open, load_collection, next_token_id,
+    sender_origin, token_owner,
+};
+use crate::msg::{
+    AccountInventoryResponse, ChainSettingsResponse, ClaimableTokensResponse, CollectionResponse,
+    CollectionToken, CollectionsResponse, ConfigResponse, HubQueryMsg, InventoryTokenResponse,
+    MetadataPermissionsResponse, NextTokenIdResponse, SupplyResponse, TokenExistsResponse,
+    TransferFeeResponse, TransferResponse, TransferSender, TransferStatus, TransfersResponse,
+};
+use crate::rate_limit::remaining_quota;
+use crate::state::{
+    account_tokens, transfers, Transfer, CHAIN_SETTINGS, COLLECTIONS, CONFIG, DEFAULT_COLLECTION,
+    METADATA_PERMISSIONS, PAUSED, PENDING_ADMIN, PENDING_CLAIMS, SUPPLY, TRANSFER_FEES,
+    TRUSTED_NAMESPACES,
+};
+use abstract_adapter::std::objects::{chain_name::ChainName, AccountId};
+use abstract_sdk::AccountVerification;
+use cosmwasm_std::{to_json_binary, Binary, Deps, Env, Order, StdResult};
+use cw721::NftInfoResponse;
+use cw721_metadata_onchain::{Extension, QueryMsg};
+use cw_storage_plus::Bound;
 
-pub fn query_handler(deps: Deps, env: Env, _app: &Hub, msg: HubQueryMsg) -> HubResult<Binary> {
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub fn query_handler(deps: Deps, env: Env, hub: &Hub, msg: HubQueryMsg) -> HubResult<Binary> {
     match msg {
         HubQueryMsg::Config {} => to_json_binary(&query_config(deps)?),
         HubQueryMsg::NextTokenId {} => to_json_binary(&query_next_token_id(deps, env)?),
+        HubQueryMsg::TransfersBySender {
+            sender,
+            status,
+            start_after,
+            limit,
+        } => to_json_binary(&query_transfers_by_sender(
+            deps,
+            sender,
+            status,
+            start_after,
+            limit,
+        )?),
+        HubQueryMsg::TransfersByToken {
+            token_id,
+            collection_id,
+            status,
+            start_after,
+            limit,
+        } => to_json_binary(&query_transfers_by_token(
+            deps,
+            collection_or_default(collection_id),
+            token_id,
+            status,
+            start_after,
+            limit,
+        )?),
+        HubQueryMsg::TokenExists {
+            token_id,
+            collection_id,
+        } => to_json_binary(&query_token_exists(
+            deps,
+            token_id,
+            collection_or_default(collection_id),
+        )?),
+        HubQueryMsg::Collections { start_after, limit } => {
+            to_json_binary(&query_collections(deps, start_after, limit)?)
+        }
+        HubQueryMsg::TransferFee { chain } => to_json_binary(&query_transfer_fee(deps, chain)?),
+        HubQueryMsg::ChainSettings { chain } => to_json_binary(&query_chain_settings(deps, chain)?),
+        HubQueryMsg::ClaimableTokens {
+            account_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_claimable_tokens(
+            deps,
+            account_id,
+            start_after,
+            limit,
+        )?),
+        HubQueryMsg::MetadataPermissions { module_id } => {
+            to_json_binary(&query_metadata_permissions(deps, module_id)?)
+        }
+        HubQueryMsg::Supply {} => to_json_binary(&query_supply(deps, env)?),
+        HubQueryMsg::RemainingQuota { sender, chain } => {
+            let origin = sender_origin(deps, &sender)?;
+            to_json_binary(&remaining_quota(deps, &env, &origin, chain)?)
+        }
+        HubQueryMsg::AccountInventory {
+            account_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_account_inventory(
+            deps,
+            hub,
+            account_id,
+            start_after,
+            limit,
+        )?),
     }
     .map_err(Into::into)
 }
 
 fn query_config(deps: Deps) -> HubResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
+    let collection = load_collection(deps.storage, DEFAULT_COLLECTION)?;
+    let trusted_namespaces = TRUSTED_NAMESPACES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
     Ok(ConfigResponse {
-        nft: NFT.load(deps.storage)?.to_string(),
+        admin_account: config.admin_account,
+        pending_admin_account: PENDING_ADMIN.may_load(deps.storage)?,
+        nft_code_id: config.nft_code_id,
+        trusted_namespaces,
+        nft: collection.nft.to_string(),
+        nft_name: collection.name,
+        nft_symbol: collection.symbol,
+        nft_label: collection.label,
         next_token_id: config.next_token_id,
+        transfer_mode: config.transfer_mode,
+        paused: PAUSED.may_load(deps.storage)?.unwrap_or_default(),
     })
 }
 
@@ -25,3 +127,246 @@ fn query_next_token_id(deps: Deps, env: Env) -> HubResult<NextTokenIdResponse> {
         next_token_id: next_token_id(deps, env)?,
     })
 }
+
+fn query_token_exists(
+    deps: Deps,
+    token_id: String,
+    collection_id: String,
+) -> HubResult<TokenExistsResponse> {
+    let collection = load_collection(deps.storage, &collection_id)?;
+    let owner = token_owner(deps, &collection.nft, &token_id);
+    Ok(TokenExistsResponse {
+        exists: owner.is_some(),
+        owner,
+        soulbound: collection.soulbound,
+    })
+}
+
+fn query_collections(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> HubResult<CollectionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let collections = COLLECTIONS
+        .range(
+            deps.storage,
+            start_after.as_deref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|collection| {
+            let (collection_id, collection) = collection?;
+            Ok(CollectionResponse {
+                collection_id,
+                nft: collection.nft.to_string(),
+                name: collection.name,
+                symbol: collection.symbol,
+                label: collection.label,
+                soulbound: collection.soulbound,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(CollectionsResponse { collections })
+}
+
+fn query_transfer_fee(deps: Deps, chain: String) -> HubResult<TransferFeeResponse> {
+    Ok(TransferFeeResponse {
+        fee: TRANSFER_FEES.may_load(deps.storage, &chain)?,
+    })
+}
+
+fn query_chain_settings(deps: Deps, chain: String) -> HubResult<ChainSettingsResponse> {
+    let settings = CHAIN_SETTINGS
+        .may_load(deps.storage, &chain)?
+        .unwrap_or_default();
+    Ok(ChainSettingsResponse {
+        allowlisted: settings.allowlisted,
+        denylisted: settings.denylisted,
+        paused: settings.paused,
+        open: ensure_chain_open(deps, &chain).is_ok(),
+    })
+}
+
+fn query_metadata_permissions(
+    deps: Deps,
+    module_id: String,
+) -> HubResult<MetadataPermissionsResponse> {
+    Ok(MetadataPermissionsResponse {
+        fields: METADATA_PERMISSIONS
+            .may_load(deps.storage, &module_id)?
+            .unwrap_or_default(),
+    })
+}
+
+fn query_supply(deps: Deps, env: Env) -> HubResult<SupplyResponse> {
+    let supply = SUPPLY.may_load(deps.storage)?.unwrap_or_default();
+    Ok(SupplyResponse {
+        chain: ChainName::from_chain_id(&env.block.chain_id).to_string(),
+        minted: supply.minted,
+        received: supply.received,
+        burned: supply.burned,
+        escrowed: supply.escrowed,
+        consumed: supply.consumed,
+        live: supply.live(),
+    })
+}
+
+fn query_claimable_tokens(
+    deps: Deps,
+    account_id: AccountId,
+    start_after: Option<CollectionToken>,
+    limit: Option<u32>,
+) -> HubResult<ClaimableTokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let tokens = PENDING_CLAIMS
+        .prefix(&account_id)
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(|token| {
+                Bound::exclusive((token.collection_id.as_str(), token.token_id.as_str()))
+            }),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|key| {
+            let (collection_id, token_id) = key?;
+            Ok(CollectionToken {
+                collection_id,
+                token_id,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ClaimableTokensResponse { tokens })
+}
+
+fn query_account_inventory(
+    deps: Deps,
+    hub: &Hub,
+    account_id: AccountId,
+    start_after: Option<CollectionToken>,
+    limit: Option<u32>,
+) -> HubResult<AccountInventoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let proxy = hub.account_registry(deps)?.account_base(&account_id)?.proxy;
+
+    let tokens = account_tokens()
+        .idx
+        .account
+        .prefix(account_id.to_string())
+        .keys(
+            deps.storage,
+            start_after.map(|token| Bound::exclusive((token.collection_id, token.token_id))),
+            None,
+            Order::Ascending,
+        )
+        .map(|key| {
+            let (collection_id, token_id) = key?;
+            let nft = collection_address(deps.storage, &collection_id)?;
+            // Tokens burned or sent away without going through the hub are skipped
+            if token_owner(deps, &nft, &token_id).as_deref() != Some(proxy.as_str()) {
+                return Ok(None);
+            }
+            let nft_info: NftInfoResponse<Extension> = deps.querier.query_wasm_smart(
+                nft,
+                &QueryMsg::NftInfo {
+                    token_id: token_id.clone(),
+                },
+            )?;
+            Ok(Some(InventoryTokenResponse {
+                token_id,
+                collection_id,
+                token_uri: nft_info.token_uri,
+                extension: nft_info.extension,
+            }))
+        })
+        .filter_map(Result::transpose)
+        .take(limit)
+        .collect::<HubResult<Vec<_>>>()?;
+
+    Ok(AccountInventoryResponse { tokens })
+}
+
+fn query_transfers_by_sender(
+    deps: Deps,
+    sender: TransferSender,
+    status: Option<TransferStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> HubResult<TransfersResponse> {
+    let transfers = transfers()
+        .idx
+        .account
+        .prefix(sender_origin(deps, &sender)?)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        );
+
+    paginate_transfers(transfers, status, limit)
+}
+
+fn query_transfers_by_token(
+    deps: Deps,
+    collection_id: String,
+    token_id: String,
+    status: Option<TransferStatus>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> HubResult<TransfersResponse> {
+    let transfers = transfers()
+        .idx
+        .token
+        .prefix((collection_id, token_id))
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        );
+
+    paginate_transfers(transfers, status, limit)
+}
+
+fn paginate_transfers(
+    transfers: impl Iterator<Item = StdResult<(u64, Transfer)>>,
+    status: Option<TransferStatus>,
+    limit: Option<u32>,
+) -> HubResult<TransfersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let transfers = transfers
+        .filter(|transfer| match (&status, transfer) {
+            (Some(status), Ok((_, transfer))) => transfer.status == *status,
+            _ => true,
+        })
+        .take(limit)
+        .map(|transfer| {
+            let (transfer_id, transfer) = transfer?;
+            Ok(TransferResponse {
+                transfer_id,
+                token_id: transfer.token_id,
+                collection_id: transfer.collection_id,
+                account_id: transfer.account_id,
+                sender: transfer.sender.to_string(),
+                destination_chain: transfer.destination_chain,
+                route: transfer.route,
+                previous_hop: transfer.previous_hop,
+                start_height: transfer.start_height,
+                status: transfer.status,
+                error: transfer.error,
+                arrival_hook_error: transfer.arrival_hook_error,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(TransfersResponse { transfers })
+}
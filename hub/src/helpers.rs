@@ -1,7 +1,29 @@
-use abstract_adapter::std::objects::chain_name::ChainName;
-use cosmwasm_std::{Deps, DepsMut, Env};
+use abstract_adapter::std::objects::{
+    account::AccountTrace, chain_name::ChainName, module::ModuleInfo, AccountId,
+};
+use abstract_adapter::std::IBC_CLIENT;
+use abstract_ibc_host::endpoints::packet::client_to_host_account_id;
+use abstract_sdk::features::AccountIdentification;
+use abstract_sdk::{AccountVerification, ModuleRegistryInterface};
+use common::NAMESPACE;
+use cosmos_adventures_nft::msg::{InstantiateMsg, NftExecuteMsg, RoyaltyInfo};
+use cosmwasm_std::{
+    instantiate2_address, to_json_binary, wasm_execute, Addr, Binary, CodeInfoResponse, Deps,
+    DepsMut, Env, Order, QueryRequest, StdResult, Storage, WasmMsg, WasmQuery,
+};
+use cw721::OwnerOfResponse;
+use cw721_metadata_onchain::{Extension, QueryMsg};
 
-use crate::{contract::HubResult, state::CONFIG};
+use crate::{
+    contract::{Hub, HubResult},
+    error::HubError,
+    msg::{Royalty, TransferSender},
+    state::{
+        account_tokens, AccountToken, ChainSettings, Collection, Supply, CHAIN_SETTINGS,
+        COLLECTIONS, COLLECTION_IDS, CONFIG, DEFAULT_COLLECTION, PAUSED, SUPPLY,
+        TRUSTED_NAMESPACES,
+    },
+};
 
 pub fn next_token_id_mut(deps: DepsMut, env: Env) -> HubResult<String> {
     let next_token_id = next_token_id(deps.as_ref(), env)?;
@@ -21,3 +43,307 @@ pub fn next_token_id(deps: Deps, env: Env) -> HubResult<String> {
 
     Ok(next_token_id)
 }
+
+/// Makes sure the hub is called on behalf of the admin account
+pub fn ensure_admin(deps: Deps, hub: &Hub) -> HubResult<()> {
+    let admin_account = CONFIG.load(deps.storage)?.admin_account;
+    if hub.account_id(deps)? != admin_account {
+        return Err(HubError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Whether the modules of the namespace are trusted by the hub
+/// The hub namespace is always trusted, the admin can trust other namespaces
+pub fn is_trusted_namespace(storage: &dyn Storage, namespace: &str) -> StdResult<bool> {
+    Ok(namespace == NAMESPACE || TRUSTED_NAMESPACES.has(storage, namespace))
+}
+
+/// Makes sure tokens can currently be exchanged with `chain`
+pub fn ensure_chain_open(deps: Deps, chain: &str) -> HubResult<()> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(HubError::Paused {});
+    }
+
+    let settings = CHAIN_SETTINGS
+        .may_load(deps.storage, chain)?
+        .unwrap_or_default();
+    if settings.paused {
+        return Err(HubError::ChainPaused(chain.to_string()));
+    }
+    if settings.denylisted || (!settings.allowlisted && allowlist_enabled(deps)) {
+        return Err(HubError::ChainNotAllowed(chain.to_string()));
+    }
+    Ok(())
+}
+
+/// The allowlist is only enforced once at least one chain is allowlisted
+pub fn allowlist_enabled(deps: Deps) -> bool {
+    CHAIN_SETTINGS
+        .range(deps.storage, None, None, Order::Ascending)
+        .any(|settings| {
+            matches!(
+                settings,
+                Ok((
+                    _,
+                    ChainSettings {
+                        allowlisted: true,
+                        ..
+                    }
+                ))
+            )
+        })
+}
+
+/// Returns the key under which the transfers and the quota usage of `sender` are recorded, as in `transfer_origin`
+pub fn sender_origin(deps: Deps, sender: &TransferSender) -> HubResult<String> {
+    Ok(match sender {
+        TransferSender::Account(account_id) => account_id.to_string(),
+        TransferSender::Address(address) => deps.api.addr_validate(address)?.to_string(),
+    })
+}
+
+/// Returns the id of the account on this chain that corresponds to `account_id` on `client_chain`
+/// If the account comes back to the chain it was created on, its trace is popped
+/// Otherwise, the client chain is added to its trace
+pub fn host_account_id(
+    env: &Env,
+    client_chain: ChainName,
+    account_id: AccountId,
+) -> HubResult<AccountId> {
+    let host_account_id = match account_id.trace() {
+        AccountTrace::Remote(trace)
+            if trace.last() == Some(&ChainName::from_chain_id(&env.block.chain_id)) =>
+        {
+            let mut new_trace = trace.clone();
+            new_trace.pop();
+            if new_trace.is_empty() {
+                AccountId::local(account_id.seq())
+            } else {
+                AccountId::remote(account_id.seq(), new_trace)?
+            }
+        }
+        _ => client_to_host_account_id(client_chain, account_id.clone()),
+    };
+
+    Ok(host_account_id)
+}
+
+/// Returns the address of the IBC client registered on this chain
+/// This is used when the hub needs to send IBC messages without acting on behalf of an account
+pub fn ibc_client_address(deps: Deps, hub: &Hub) -> HubResult<Addr> {
+    let ibc_client = hub
+        .module_registry(deps)?
+        .query_module(ModuleInfo::from_id_latest(IBC_CLIENT)?)?;
+
+    Ok(ibc_client.reference.unwrap_native()?)
+}
+
+/// Returns the name of the chain the token was minted on
+/// Token ids are created as `chain>n` by the hub of their home chain
+pub fn token_home_chain(token_id: &str) -> Option<&str> {
+    token_id.split_once('>').map(|(home_chain, _)| home_chain)
+}
+
+/// Returns the current owner of the token, or `None` if it doesn't exist in the NFT contract
+pub fn token_owner(deps: Deps, nft: &Addr, token_id: &str) -> Option<String> {
+    deps.querier
+        .query_wasm_smart::<OwnerOfResponse>(
+            nft,
+            &QueryMsg::OwnerOf {
+                token_id: token_id.to_string(),
+                include_expired: None,
+            },
+        )
+        .ok()
+        .map(|owner| owner.owner)
+}
+
+/// Creates the message that sets the cw2981 royalty of a token in its NFT contract
+/// The royalty is paid to the proxy of the recipient account, it isn't set if that account doesn't exist on this chain
+pub fn royalty_msg(
+    deps: Deps,
+    hub: &Hub,
+    nft: &Addr,
+    token_id: &str,
+    royalty: &Royalty,
+) -> HubResult<Option<WasmMsg>> {
+    let Ok(account_base) = hub.account_registry(deps)?.account_base(&royalty.recipient) else {
+        return Ok(None);
+    };
+    let msg = cosmos_adventures_nft::msg::ExecuteMsg::Extension {
+        msg: NftExecuteMsg::SetRoyalty {
+            token_id: token_id.to_string(),
+            royalty: Some(RoyaltyInfo {
+                payment_address: account_base.proxy.to_string(),
+                share: royalty.share,
+            }),
+        },
+    };
+    Ok(Some(wasm_execute(nft, &msg, vec![])?))
+}
+
+/// Creates the message that replaces the token uri and the metadata of a token in its NFT contract
+pub fn update_metadata_msg(
+    nft: &Addr,
+    token_id: &str,
+    token_uri: Option<String>,
+    extension: Extension,
+) -> StdResult<WasmMsg> {
+    let msg = cosmos_adventures_nft::msg::ExecuteMsg::Extension {
+        msg: NftExecuteMsg::UpdateMetadata {
+            token_id: token_id.to_string(),
+            token_uri,
+            extension,
+        },
+    };
+    wasm_execute(nft, &msg, vec![])
+}
+
+/// Updates the supply counters of this chain
+pub fn update_supply(storage: &mut dyn Storage, action: impl FnOnce(&mut Supply)) -> StdResult<()> {
+    let mut supply = SUPPLY.may_load(storage)?.unwrap_or_default();
+    action(&mut supply);
+    SUPPLY.save(storage, &supply)
+}
+
+/// Adds the token to the inventory of the account
+pub fn index_token(
+    storage: &mut dyn Storage,
+    account_id: &AccountId,
+    collection_id: &str,
+    token_id: &str,
+) -> StdResult<()> {
+    account_tokens().save(
+        storage,
+        (collection_id, token_id),
+        &AccountToken {
+            account_id: account_id.clone(),
+        },
+    )
+}
+
+/// Removes the token from the inventory of the account holding it, if any
+/// Returns that account, so that the token can be indexed again if it comes back
+pub fn unindex_token(
+    storage: &mut dyn Storage,
+    collection_id: &str,
+    token_id: &str,
+) -> StdResult<Option<AccountId>> {
+    let indexed = account_tokens().may_load(storage, (collection_id, token_id))?;
+    account_tokens().remove(storage, (collection_id, token_id))?;
+    Ok(indexed.map(|token| token.account_id))
+}
+
+/// Returns the collection id, or the id of the collection created with the hub if none is specified
+pub fn collection_or_default(collection_id: Option<String>) -> String {
+    collection_id.unwrap_or_else(|| DEFAULT_COLLECTION.to_string())
+}
+
+pub fn load_collection(storage: &dyn Storage, collection_id: &str) -> HubResult<Collection> {
+    COLLECTIONS
+        .may_load(storage, collection_id)?
+        .ok_or_else(|| HubError::CollectionNotFound(collection_id.to_string()))
+}
+
+/// Saves the collection along with the collection id of its NFT contract
+pub fn save_collection(
+    storage: &mut dyn Storage,
+    collection_id: &str,
+    collection: &Collection,
+) -> StdResult<()> {
+    COLLECTIONS.save(storage, collection_id, collection)?;
+    COLLECTION_IDS.save(storage, &collection.nft, &collection_id.to_string())
+}
+
+/// Returns the NFT contract of the collection
+pub fn collection_address(storage: &dyn Storage, collection_id: &str) -> HubResult<Addr> {
+    Ok(load_collection(storage, collection_id)?.nft)
+}
+
+/// Returns the id of the collection whose NFT contract is `nft`
+pub fn collection_id_of(storage: &dyn Storage, nft: &Addr) -> HubResult<String> {
+    COLLECTION_IDS
+        .may_load(storage, nft)?
+        .ok_or(HubError::UnknownCollection {})
+}
+
+/// Returns the NFT contract of a collection whose tokens can leave their account
+pub fn transferable_collection(storage: &dyn Storage, collection_id: &str) -> HubResult<Addr> {
+    let collection = load_collection(storage, collection_id)?;
+    if collection.soulbound {
+        return Err(HubError::Soulbound(collection_id.to_string()));
+    }
+    Ok(collection.nft)
+}
+
+/// Creates the message that instantiates the NFT contract of a collection created after the hub
+/// Each collection has its own salt, so that its NFT contract has a different address
+pub fn new_collection(
+    deps: Deps,
+    env: &Env,
+    collection_id: &str,
+    name: String,
+    symbol: String,
+    soulbound: bool,
+) -> HubResult<(WasmMsg, Collection)> {
+    let config = CONFIG.load(deps.storage)?;
+    instantiate_collection(
+        deps,
+        env,
+        config.nft_code_id,
+        format!("collection:{collection_id}").as_bytes(),
+        format!("Cosmos Adventures {collection_id} NFT"),
+        name,
+        symbol,
+        soulbound,
+    )
+}
+
+/// Creates the message that instantiates the NFT contract of a collection, minted by the hub
+/// The contract is instantiated with `instantiate2`, so that its address is known right away
+/// The NFT contract of soulbound collections blocks the transfers of their tokens
+#[allow(clippy::too_many_arguments)]
+pub fn instantiate_collection(
+    deps: Deps,
+    env: &Env,
+    code_id: u64,
+    salt: &[u8],
+    label: String,
+    name: String,
+    symbol: String,
+    soulbound: bool,
+) -> HubResult<(WasmMsg, Collection)> {
+    let msg = to_json_binary(&InstantiateMsg {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        minter: env.contract.address.to_string(),
+        soulbound,
+    })?;
+    let instantiate_msg = WasmMsg::Instantiate2 {
+        admin: Some(env.contract.address.to_string()),
+        code_id,
+        label: label.clone(),
+        msg,
+        funds: vec![],
+        salt: Binary(salt.to_vec()),
+    };
+
+    let code_info: CodeInfoResponse = deps
+        .querier
+        .query(&QueryRequest::Wasm(WasmQuery::CodeInfo { code_id }))?;
+    let canon_nft = instantiate2_address(
+        code_info.checksum.as_slice(),
+        &deps.api.addr_canonicalize(env.contract.address.as_str())?,
+        salt,
+    )?;
+
+    let collection = Collection {
+        nft: deps.api.addr_humanize(&canon_nft)?,
+        name,
+        symbol,
+        label,
+        soulbound,
+    };
+    Ok((instantiate_msg, collection))
+}
@@ -0,0 +1,333 @@
+//! ICS-721 bridge between the hub collections and standard ics721 deployments
+//!
+//! Tokens sent through this bridge are escrowed in the hub and leave with their NFT contract address as class id.
+//! Only tokens of the hub collections that come back through the channel they left on are accepted.
+use std::collections::BTreeSet;
+
+use abstract_adapter::std::objects::AccountId;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::{Deserialize, Serialize};
+use cosmwasm_std::{
+    from_json, to_json_binary, wasm_execute, Addr, Binary, DepsMut, Empty, Env, IbcBasicResponse,
+    IbcChannel, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg,
+    IbcChannelOpenResponse, IbcMsg, IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg,
+    IbcPacketTimeoutMsg, IbcReceiveResponse, IbcTimeout,
+};
+use cw721_metadata_onchain::ExecuteMsg;
+
+use crate::{
+    contract::HubResult,
+    error::HubError,
+    helpers::{collection_id_of, ensure_chain_open, index_token, update_supply},
+    msg::IbcNft,
+    state::{Ics721Escrow, ICS721_CHANNELS, ICS721_ESCROW},
+};
+
+pub const ICS721_VERSION: &str = "ics721-1";
+/// Packets that are not relayed after this delay are given back to their sender
+pub const ICS721_PACKET_LIFETIME: u64 = 60 * 60 * 24;
+
+/// Standard ICS-721 packet data
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "cosmwasm_schema::serde", rename_all = "camelCase")]
+pub struct NonFungibleTokenPacketData {
+    pub class_id: String,
+    pub class_uri: Option<String>,
+    pub class_data: Option<Binary>,
+    pub token_ids: Vec<String>,
+    pub token_uris: Option<Vec<String>>,
+    /// Json encoded `cw721_metadata_onchain` extension of each token
+    pub token_data: Option<Vec<Binary>>,
+    pub sender: String,
+    pub receiver: String,
+    pub memo: Option<String>,
+}
+
+/// Standard ICS-20 style acknowledgement used by ICS-721
+#[cw_serde]
+pub enum Ics721Ack {
+    Result(Binary),
+    Error(String),
+}
+
+impl Ics721Ack {
+    pub fn success() -> Binary {
+        to_json_binary(&Ics721Ack::Result(Binary::from(vec![1]))).unwrap()
+    }
+
+    pub fn fail(error: String) -> Binary {
+        to_json_binary(&Ics721Ack::Error(error)).unwrap()
+    }
+}
+
+/// Creates the ICS-721 packet that sends escrowed tokens of the `nft` collection to `receiver` on the counterparty chain
+#[allow(clippy::too_many_arguments)]
+pub fn send_packet(
+    deps: DepsMut,
+    env: &Env,
+    nft: &Addr,
+    channel_id: String,
+    sender: Addr,
+    account_id: AccountId,
+    receiver: String,
+    memo: Option<String>,
+    tokens: Vec<IbcNft>,
+) -> HubResult<IbcMsg> {
+    if !ICS721_CHANNELS.has(deps.storage, &channel_id) {
+        return Err(HubError::UnknownChannel(channel_id));
+    }
+
+    let collection_id = collection_id_of(deps.storage, nft)?;
+    let mut token_ids = vec![];
+    let mut token_uris = vec![];
+    let mut token_data = vec![];
+    for token in tokens {
+        ICS721_ESCROW.save(
+            deps.storage,
+            (&collection_id, &token.token_id),
+            &Ics721Escrow {
+                channel_id: channel_id.clone(),
+                account_id: account_id.clone(),
+            },
+        )?;
+        update_supply(deps.storage, |supply| supply.escrowed += 1)?;
+        token_uris.push(token.token_uri.unwrap_or_default());
+        token_data.push(to_json_binary(&token.extension)?);
+        token_ids.push(token.token_id);
+    }
+
+    let packet = NonFungibleTokenPacketData {
+        class_id: nft.to_string(),
+        class_uri: None,
+        class_data: None,
+        token_ids,
+        token_uris: Some(token_uris),
+        token_data: Some(token_data),
+        sender: sender.to_string(),
+        receiver,
+        memo,
+    };
+
+    Ok(IbcMsg::SendPacket {
+        channel_id,
+        data: to_json_binary(&packet)?,
+        timeout: IbcTimeout::with_timestamp(env.block.time.plus_seconds(ICS721_PACKET_LIFETIME)),
+    })
+}
+
+pub fn channel_open(msg: IbcChannelOpenMsg) -> HubResult<IbcChannelOpenResponse> {
+    validate_channel(msg.channel(), msg.counterparty_version())?;
+    Ok(None)
+}
+
+pub fn channel_connect(deps: DepsMut, msg: IbcChannelConnectMsg) -> HubResult<IbcBasicResponse> {
+    validate_channel(msg.channel(), msg.counterparty_version())?;
+
+    let channel_id = &msg.channel().endpoint.channel_id;
+    ICS721_CHANNELS.save(deps.storage, channel_id, &Empty {})?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ics721_channel_connect")
+        .add_attribute("channel_id", channel_id))
+}
+
+pub fn channel_close(deps: DepsMut, msg: IbcChannelCloseMsg) -> HubResult<IbcBasicResponse> {
+    // Closing the channel from this side would lock the escrowed tokens forever
+    // A close confirmed by the counterparty can't be refused, the channel is only forgotten
+    let IbcChannelCloseMsg::CloseConfirm { channel } = msg else {
+        return Err(HubError::ChannelClose {});
+    };
+
+    let channel_id = &channel.endpoint.channel_id;
+    ICS721_CHANNELS.remove(deps.storage, channel_id);
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("method", "ics721_channel_close")
+        .add_attribute("channel_id", channel_id))
+}
+
+pub fn packet_receive(deps: DepsMut, msg: IbcPacketReceiveMsg) -> HubResult<IbcReceiveResponse> {
+    // Errors are sent back to the counterparty in the acknowledgement
+    match receive_tokens(deps, &msg.packet) {
+        Ok(response) => Ok(response),
+        Err(error) => Ok(IbcReceiveResponse::new()
+            .set_ack(Ics721Ack::fail(error.to_string()))
+            .add_attribute("method", "ics721_packet_receive")
+            .add_attribute("error", error.to_string())),
+    }
+}
+
+pub fn packet_ack(deps: DepsMut, msg: IbcPacketAckMsg) -> HubResult<IbcBasicResponse> {
+    let ack: Ics721Ack = from_json(&msg.acknowledgement.data)?;
+    match ack {
+        // The tokens stay escrowed until they come back
+        Ics721Ack::Result(_) => {
+            Ok(IbcBasicResponse::new().add_attribute("method", "ics721_packet_ack"))
+        }
+        Ics721Ack::Error(error) => refund_tokens(deps, &msg.original_packet, error),
+    }
+}
+
+pub fn packet_timeout(deps: DepsMut, msg: IbcPacketTimeoutMsg) -> HubResult<IbcBasicResponse> {
+    refund_tokens(deps, &msg.packet, "timeout".to_string())
+}
+
+fn validate_channel(channel: &IbcChannel, counterparty_version: Option<&str>) -> HubResult<()> {
+    if channel.order != IbcOrder::Unordered {
+        return Err(HubError::InvalidChannel(
+            "ICS-721 channels must be unordered".to_string(),
+        ));
+    }
+    if channel.version != ICS721_VERSION {
+        return Err(HubError::InvalidChannel(format!(
+            "ICS-721 channels must use version {ICS721_VERSION}"
+        )));
+    }
+    if let Some(version) = counterparty_version {
+        if version != ICS721_VERSION {
+            return Err(HubError::InvalidChannel(format!(
+                "ICS-721 channels must use version {ICS721_VERSION}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Gives the escrowed tokens that come back through their channel to the receiver
+fn receive_tokens(deps: DepsMut, packet: &IbcPacket) -> HubResult<IbcReceiveResponse> {
+    let data: NonFungibleTokenPacketData = from_json(&packet.data)?;
+    ensure_chain_open(deps.as_ref(), &packet.dest.channel_id)?;
+
+    // Only tokens that left a hub collection through this channel can come back
+    let source_prefix = format!("{}/{}/", packet.src.port_id, packet.src.channel_id);
+    let (nft, collection_id) = data
+        .class_id
+        .strip_prefix(&source_prefix)
+        .and_then(|nft| deps.api.addr_validate(nft).ok())
+        .and_then(|nft| {
+            let collection_id = collection_id_of(deps.storage, &nft).ok()?;
+            Some((nft, collection_id))
+        })
+        .ok_or_else(|| HubError::UnknownClass(data.class_id.clone()))?;
+    let receiver = deps.api.addr_validate(&data.receiver)?;
+
+    // Errors are turned into an acknowledgement without reverting the state, so all tokens are checked before any change
+    let mut received_ids = BTreeSet::new();
+    for token_id in &data.token_ids {
+        if !received_ids.insert(token_id.as_str()) {
+            return Err(HubError::DuplicateToken(token_id.clone()));
+        }
+        let channel_id = ICS721_ESCROW
+            .may_load(deps.storage, (&collection_id, token_id))?
+            .map(|escrow| escrow.channel_id);
+        if channel_id.as_ref() != Some(&packet.dest.channel_id) {
+            return Err(HubError::NotEscrowed(token_id.clone()));
+        }
+    }
+
+    let mut msgs = vec![];
+    for token_id in data.token_ids {
+        ICS721_ESCROW.remove(deps.storage, (&collection_id, &token_id));
+        update_supply(deps.storage, |supply| supply.escrowed -= 1)?;
+
+        msgs.push(wasm_execute(
+            &nft,
+            &ExecuteMsg::TransferNft {
+                recipient: receiver.to_string(),
+                token_id,
+            },
+            vec![],
+        )?);
+    }
+
+    Ok(IbcReceiveResponse::new()
+        .set_ack(Ics721Ack::success())
+        .add_messages(msgs)
+        .add_attribute("method", "ics721_packet_receive")
+        .add_attribute("receiver", receiver))
+}
+
+/// Gives the escrowed tokens of a failed packet back to their sender
+/// The tokens are indexed again in the inventory of the account that sent them
+fn refund_tokens(deps: DepsMut, packet: &IbcPacket, error: String) -> HubResult<IbcBasicResponse> {
+    let data: NonFungibleTokenPacketData = from_json(&packet.data)?;
+    // Packets sent by the hub use the NFT contract address as class id
+    let nft = deps.api.addr_validate(&data.class_id)?;
+    let collection_id = collection_id_of(deps.storage, &nft)?;
+
+    let mut msgs = vec![];
+    for token_id in data.token_ids {
+        if let Some(escrow) = ICS721_ESCROW.may_load(deps.storage, (&collection_id, &token_id))? {
+            index_token(deps.storage, &escrow.account_id, &collection_id, &token_id)?;
+        }
+        ICS721_ESCROW.remove(deps.storage, (&collection_id, &token_id));
+        update_supply(deps.storage, |supply| supply.escrowed -= 1)?;
+        msgs.push(wasm_execute(
+            &nft,
+            &ExecuteMsg::TransferNft {
+                recipient: data.sender.clone(),
+                token_id,
+            },
+            vec![],
+        )?);
+    }
+
+    Ok(IbcBasicResponse::new()
+        .add_messages(msgs)
+        .add_attribute("method", "ics721_refund")
+        .add_attribute("error", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::PAUSED;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_ibc_channel_close_confirm, mock_ibc_channel_close_init,
+        mock_ibc_packet_recv,
+    };
+
+    #[test]
+    fn only_the_counterparty_can_close_a_channel() {
+        let mut deps = mock_dependencies();
+        ICS721_CHANNELS
+            .save(deps.as_mut().storage, "channel-0", &Empty {})
+            .unwrap();
+
+        let close_init =
+            mock_ibc_channel_close_init("channel-0", IbcOrder::Unordered, ICS721_VERSION);
+        let err = channel_close(deps.as_mut(), close_init).unwrap_err();
+        assert_eq!(err, HubError::ChannelClose {});
+        assert!(ICS721_CHANNELS.has(deps.as_ref().storage, "channel-0"));
+
+        let close_confirm =
+            mock_ibc_channel_close_confirm("channel-0", IbcOrder::Unordered, ICS721_VERSION);
+        channel_close(deps.as_mut(), close_confirm).unwrap();
+        assert!(!ICS721_CHANNELS.has(deps.as_ref().storage, "channel-0"));
+    }
+
+    #[test]
+    fn packets_are_refused_while_the_hub_is_paused() {
+        let mut deps = mock_dependencies();
+        PAUSED.save(deps.as_mut().storage, &true).unwrap();
+        let packet = NonFungibleTokenPacketData {
+            class_id: "transfer/channel-1/nft".to_string(),
+            class_uri: None,
+            class_data: None,
+            token_ids: vec!["juno>0".to_string()],
+            token_uris: None,
+            token_data: None,
+            sender: "sender".to_string(),
+            receiver: "receiver".to_string(),
+            memo: None,
+        };
+        let msg = mock_ibc_packet_recv("channel-0", &packet).unwrap();
+
+        let response = packet_receive(deps.as_mut(), msg).unwrap();
+        assert_eq!(
+            response.acknowledgement,
+            Ics721Ack::fail(HubError::Paused {}.to_string())
+        );
+        assert!(response.messages.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod ics721;
 pub mod module_ibc;
 pub mod transfer;
 pub const TRANSFER_CALLBACK: &str = "TRANSFER_CALLBACK";
@@ -1,18 +1,23 @@
 use crate::{
     contract::{Hub, HubResult},
-    msg::HubIbcMsg,
-    state::NFT,
+    error::HubError,
+    helpers::{ensure_chain_open, host_account_id, index_token, is_trusted_namespace},
+    ibc::transfer::{forward_tokens, receive_route_result, receive_tokens, OutboundTransfer},
+    msg::{ArrivalHook, HubHookMsg, HubIbcMsg, IbcCollection, IbcNft, Recipient},
+    replies::{arrival_hook::ARRIVAL_HOOK_ERROR, ARRIVAL_HOOK_REPLY_ID},
+    state::PENDING_CLAIMS,
 };
 use abstract_adapter::std::{
+    app,
     ibc::ModuleIbcMsg,
-    objects::{chain_name::ChainName, AccountId},
+    manager::{self, ModuleAddressesResponse},
+    objects::{
+        chain_name::ChainName, module::ModuleInfo, module_reference::ModuleReference, AccountId,
+    },
+    version_control::AccountBase,
 };
-use abstract_ibc_host::endpoints::packet::client_to_host_account_id;
-use abstract_sdk::AccountVerification;
-use common::NAMESPACE;
-use cosmwasm_std::{from_json, wasm_execute, DepsMut, Env, Response};
-use cw721_base::MintMsg;
-use cw721_metadata_onchain::{ExecuteMsg, Extension};
+use abstract_sdk::{AccountVerification, ModuleRegistryInterface};
+use cosmwasm_std::{from_json, wasm_execute, Deps, DepsMut, Empty, Env, Response, SubMsg};
 
 pub fn receive_module_ibc(
     deps: DepsMut,
@@ -21,85 +26,271 @@ pub fn receive_module_ibc(
     msg: ModuleIbcMsg,
 ) -> HubResult<Response> {
     // First we verify the calling module has the right namespace
-    // We trust all of our modules that are in the same namespace across IBC
+    // We trust all of our modules that are in the same namespace across IBC, as well as the namespaces trusted by the admin
     // This is used for extensions that will share a namespace and have a right to execute actions across the protocol
 
-    if msg.source_module.namespace.as_str().ne(NAMESPACE) {
-        return Err(crate::error::HubError::Unauthorized {});
+    if !is_trusted_namespace(deps.storage, msg.source_module.namespace.as_str())? {
+        return Err(HubError::Unauthorized {});
     }
 
     // Now we can receive the IBC message
     let decoded_message: HubIbcMsg = from_json(&msg.msg)?;
 
     match decoded_message {
+        // The tokens are only passing through this chain
         HubIbcMsg::IbcMint {
-            token_id,
-            token_uri,
-            extension,
+            tokens,
+            collection,
+            recipient,
+            fallback_address,
             local_account_id,
-        } => internal_ibc_mint_token(
+            route,
+            on_arrival,
+        } if !route.is_empty() => {
+            ensure_chain_open(deps.as_ref(), msg.client_chain.as_str())?;
+            let tokens = localize_royalties(&env, &msg.client_chain, tokens)?;
+            for chain in &route {
+                ensure_chain_open(deps.as_ref(), chain)?;
+            }
+            let account_id = local_account_id
+                .map(|account_id| host_account_id(&env, msg.client_chain.clone(), account_id))
+                .transpose()?;
+            forward_tokens(
+                deps,
+                &env,
+                &app,
+                msg.client_chain.clone(),
+                &collection,
+                OutboundTransfer {
+                    account_id,
+                    sender: env.contract.address.clone(),
+                    // The tokens are held by this hub while they are forwarded
+                    indexed_account: None,
+                    collection_id: collection.collection_id.clone(),
+                    recipient,
+                    fallback_address,
+                    tokens,
+                    path: route,
+                    previous_hop: Some(msg.client_chain.to_string()),
+                    on_arrival,
+                },
+            )
+        }
+        HubIbcMsg::IbcMint {
+            tokens,
+            collection,
+            recipient,
+            fallback_address,
+            local_account_id,
+            route: _,
+            on_arrival,
+        } => {
+            ensure_chain_open(deps.as_ref(), msg.client_chain.as_str())?;
+            let tokens = localize_royalties(&env, &msg.client_chain, tokens)?;
+            internal_ibc_mint_token(
+                deps,
+                env,
+                app,
+                msg.client_chain,
+                local_account_id,
+                collection,
+                recipient,
+                fallback_address,
+                tokens,
+                on_arrival,
+            )
+        }
+        // Results of forwarded transfers are always accepted so that the tokens in flight are settled
+        HubIbcMsg::RouteResult {
+            collection_id,
+            token_ids,
+            error,
+        } => receive_route_result(
             deps,
-            env,
+            &env,
             app,
             msg.client_chain,
-            local_account_id,
-            token_id,
-            token_uri,
-            extension,
+            collection_id,
+            token_ids,
+            error,
         ),
     }
 }
 
+/// Royalty recipients are identified as seen from the source chain, they are converted to the ids of this chain
+fn localize_royalties(
+    env: &Env,
+    client_chain: &ChainName,
+    tokens: Vec<IbcNft>,
+) -> HubResult<Vec<IbcNft>> {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let Some(royalty) = &mut token.royalty {
+                royalty.recipient =
+                    host_account_id(env, client_chain.clone(), royalty.recipient.clone())?;
+            }
+            Ok(token)
+        })
+        .collect()
+}
+
 #[allow(clippy::too_many_arguments)]
 fn internal_ibc_mint_token(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     hub: Hub,
     client_chain: ChainName,
-    account_id: AccountId,
-    token_id: String,
-    token_uri: Option<String>,
-    extension: Extension,
+    account_id: Option<AccountId>,
+    collection: IbcCollection,
+    recipient: Option<Recipient>,
+    fallback_address: Option<String>,
+    tokens: Vec<IbcNft>,
+    on_arrival: Option<ArrivalHook>,
 ) -> HubResult {
+    let source_chain = client_chain.to_string();
+    let token_ids = tokens.iter().map(|token| token.token_id.clone()).collect();
+
     // We get the new owner address
-    // This corresponds to an distant account or a local account depending on local_account_id.trace
-    // We mint a token on the app's local account
-    let target_account = if account_id.is_remote() {
-        match account_id.trace() {
-            abstract_adapter::std::objects::account::AccountTrace::Local => unreachable!(),
-            abstract_adapter::std::objects::account::AccountTrace::Remote(trace) => {
-                if trace.last() == Some(&ChainName::from_chain_id(&env.block.chain_id)) {
-                    let mut new_trace = trace.clone();
-                    new_trace.pop();
-                    if new_trace.is_empty() {
-                        AccountId::local(account_id.seq())
-                    } else {
-                        AccountId::remote(account_id.seq(), new_trace)?
-                    }
-                } else {
-                    client_to_host_account_id(client_chain.clone(), account_id.clone())
-                }
-            }
+    // This is the recipient if one was specified
+    // Otherwise this corresponds to an distant account or a local account depending on local_account_id.trace
+    let target_account = match recipient {
+        Some(Recipient::Account(recipient_account)) => recipient_account,
+        Some(Recipient::Address(address)) => {
+            let owner = deps.api.addr_validate(&address)?;
+            let msgs = receive_tokens(deps, &env, &hub, &collection, &owner, tokens)?;
+            let response = Response::new().add_messages(msgs);
+            return match on_arrival {
+                Some(_) => Ok(report_arrival_hook_error(
+                    response,
+                    "Arrival hooks need a recipient account",
+                )),
+                None => Ok(response),
+            };
+        }
+        None => {
+            // Tokens sent by plain addresses always specify a recipient
+            let account_id = account_id.ok_or(HubError::MissingRecipient {})?;
+            host_account_id(&env, client_chain, account_id)?
         }
-    } else {
-        client_to_host_account_id(client_chain.clone(), account_id.clone())
     };
 
-    let resolved_account = hub
+    // If the account doesn't exist on this chain yet, the tokens go to the fallback address
+    // Without a fallback address, they are held by the hub until the account claims them
+    let account_base = hub
         .account_registry(deps.as_ref())?
-        .account_base(&target_account)?;
+        .account_base(&target_account)
+        .ok();
+    let (owner, pending) = match (&account_base, fallback_address) {
+        (Some(account_base), _) => (account_base.proxy.clone(), false),
+        (None, Some(fallback_address)) => (deps.api.addr_validate(&fallback_address)?, false),
+        (None, None) => (env.contract.address.clone(), true),
+    };
+
+    // All the tokens are received in the same packet, if one fails, they all fail
+    let msgs = receive_tokens(
+        deps.branch(),
+        &env,
+        &hub,
+        &collection,
+        &owner,
+        tokens.clone(),
+    )?;
 
-    // The admin of the NFT is the contract here
-    let msg = wasm_execute(
-        NFT.load(deps.storage)?,
-        &ExecuteMsg::Mint(MintMsg {
-            token_id,
-            owner: resolved_account.proxy.to_string(),
-            token_uri,
-            extension,
-        }),
+    let mut response = Response::new().add_messages(msgs);
+    if account_base.is_some() {
+        for token in &tokens {
+            index_token(
+                deps.storage,
+                &target_account,
+                &collection.collection_id,
+                &token.token_id,
+            )?;
+        }
+    }
+    if pending {
+        for token in &tokens {
+            PENDING_CLAIMS.save(
+                deps.storage,
+                (&target_account, &collection.collection_id, &token.token_id),
+                &Empty {},
+            )?;
+        }
+        response = response.add_attribute("pending_claim", target_account.to_string());
+    }
+
+    let Some(on_arrival) = on_arrival else {
+        return Ok(response);
+    };
+    let Some(account_base) = account_base else {
+        return Ok(report_arrival_hook_error(
+            response,
+            "Recipient account doesn't exist on this chain",
+        ));
+    };
+    match arrival_hook_msg(
+        deps.as_ref(),
+        &hub,
+        &account_base,
+        on_arrival,
+        token_ids,
+        source_chain,
+    ) {
+        Ok(hook_msg) => Ok(response.add_submessage(hook_msg)),
+        Err(error) => Ok(report_arrival_hook_error(response, error)),
+    }
+}
+
+/// Creates the message that calls the arrival hook app installed on the recipient account
+/// Adapters only accept requests of the addresses authorized by the account, which the hub isn't, so they can't be hooks
+/// Its failure is caught in a reply so that the tokens are still received
+fn arrival_hook_msg(
+    deps: Deps,
+    hub: &Hub,
+    account_base: &AccountBase,
+    on_arrival: ArrivalHook,
+    token_ids: Vec<String>,
+    source_chain: String,
+) -> HubResult<SubMsg> {
+    // Only modules of trusted namespaces can be called by the hub
+    let module_id = on_arrival.module_id;
+    let namespace = ModuleInfo::from_id_latest(&module_id)?.namespace;
+    if !is_trusted_namespace(deps.storage, namespace.as_str())? {
+        return Err(HubError::WrongNamespace {});
+    }
+
+    let module_addresses: ModuleAddressesResponse = deps.querier.query_wasm_smart(
+        &account_base.manager,
+        &manager::QueryMsg::ModuleAddresses {
+            ids: vec![module_id.clone()],
+        },
+    )?;
+    let (_, module_address) = module_addresses
+        .modules
+        .into_iter()
+        .next()
+        .ok_or_else(|| HubError::ModuleNotInstalled(module_id.clone()))?;
+
+    let hook = HubHookMsg::OnArrival {
+        token_ids,
+        source_chain,
+        msg: on_arrival.msg,
+    };
+    let module = hub
+        .module_registry(deps)?
+        .query_module(ModuleInfo::from_id_latest(&module_id)?)?;
+    let ModuleReference::App(_) = module.reference else {
+        return Err(HubError::NotAHookModule(module_id));
+    };
+    let hook_msg = wasm_execute(
+        module_address,
+        &app::ExecuteMsg::<HubHookMsg>::Module(hook),
         vec![],
     )?;
+    Ok(SubMsg::reply_on_error(hook_msg, ARRIVAL_HOOK_REPLY_ID))
+}
 
-    Ok(Response::new().add_message(msg))
+/// The error is added to the response events, which are part of the acknowledgement sent back to the source chain
+fn report_arrival_hook_error(response: Response, error: impl ToString) -> Response {
+    response.add_attribute(ARRIVAL_HOOK_ERROR, error.to_string())
 }
@@ -1,49 +1,802 @@
-use abstract_adapter::std::ibc::{CallbackResult, IbcResponseMsg};
+use std::collections::{BTreeMap, BTreeSet};
+
+use abstract_adapter::std::ibc::{CallbackInfo, CallbackResult, IbcResponseMsg};
+use abstract_adapter::std::ibc_client;
+use abstract_adapter::std::objects::{chain_name::ChainName, module::ModuleInfo, AccountId};
+use abstract_sdk::features::ModuleIdentification;
 use abstract_sdk::AbstractResponse;
-use cosmwasm_std::{from_json, wasm_execute, DepsMut, Env, MessageInfo};
+use cosmwasm_std::{
+    from_json, to_json_binary, wasm_execute, Addr, CosmosMsg, Deps, DepsMut, Empty, Env,
+    MessageInfo, Order, Response, StdResult, WasmMsg,
+};
 
 use crate::{
     contract::{Hub, HubResult},
     error::HubError,
-    msg::HubIbcCallbackMsg,
-    state::NFT,
+    handlers::migrate::legacy_token_walked,
+    helpers::{
+        collection_address, ibc_client_address, index_token, load_collection, new_collection,
+        royalty_msg, save_collection, token_home_chain, token_owner, update_metadata_msg,
+        update_supply,
+    },
+    msg::{
+        ArrivalHook, HubIbcCallbackMsg, HubIbcMsg, IbcCollection, IbcNft, Recipient, TransferMode,
+        TransferStatus,
+    },
+    state::{
+        transfers, RouteResult, Transfer, COLLECTIONS, CONFIG, DEFAULT_COLLECTION, ESCROWED_TOKENS,
+        NEXT_TRANSFER_ID, ROYALTIES, UNDELIVERED_ROUTE_RESULTS,
+    },
 };
 use cw721_metadata_onchain::ExecuteMsg;
 
+use super::TRANSFER_CALLBACK;
+use crate::replies::arrival_hook::ARRIVAL_HOOK_ERROR;
+
+/// Tokens escrowed in the hub that need to be sent to another chain
+pub struct OutboundTransfer {
+    /// Account the tokens were escrowed from, as identified on this chain
+    /// `None` if they were sent by a plain address
+    pub account_id: Option<AccountId>,
+    /// Address the tokens were escrowed from
+    pub sender: Addr,
+    /// Account whose inventory held the tokens, they are indexed under it again if they are refunded
+    pub indexed_account: Option<AccountId>,
+    pub collection_id: String,
+    pub recipient: Option<Recipient>,
+    pub fallback_address: Option<String>,
+    pub tokens: Vec<IbcNft>,
+    /// Chains the tokens go through, the last one being their destination
+    pub path: Vec<String>,
+    /// Chain the tokens come from, if this hub only forwards them
+    pub previous_hop: Option<String>,
+    pub on_arrival: Option<ArrivalHook>,
+}
+
+/// Registers the transfers and creates the IBC message that sends the tokens to the first chain of their path
+pub fn send_tokens(
+    deps: DepsMut,
+    env: &Env,
+    hub: &Hub,
+    ibc_client: Addr,
+    transfer: OutboundTransfer,
+) -> HubResult<(CosmosMsg, Vec<u64>)> {
+    let destination_chain = transfer
+        .path
+        .last()
+        .cloned()
+        .ok_or(HubError::EmptyRoute {})?;
+
+    // We register the transfers until the callback settles them
+    let mut transfer_ids = vec![];
+    for token in &transfer.tokens {
+        let transfer_id = NEXT_TRANSFER_ID.may_load(deps.storage)?.unwrap_or_default();
+        NEXT_TRANSFER_ID.save(deps.storage, &(transfer_id + 1))?;
+        transfers().save(
+            deps.storage,
+            transfer_id,
+            &Transfer {
+                token_id: token.token_id.clone(),
+                collection_id: transfer.collection_id.clone(),
+                account_id: transfer.account_id.clone(),
+                sender: transfer.sender.clone(),
+                indexed_account: transfer.indexed_account.clone(),
+                destination_chain: destination_chain.clone(),
+                route: transfer.path.clone(),
+                previous_hop: transfer.previous_hop.clone(),
+                start_height: env.block.height,
+                status: TransferStatus::Pending,
+                error: None,
+                arrival_hook_error: None,
+            },
+        )?;
+        transfer_ids.push(transfer_id);
+    }
+
+    // The collection name and symbol go along with the tokens, in case the next hub doesn't have the collection yet
+    let collection = load_collection(deps.storage, &transfer.collection_id)?;
+
+    // We send a single IBC mint message with all the tokens to the next chain
+    // The chains after it are forwarded the tokens by their hub
+    let mut route = transfer.path;
+    let next_hop = route.remove(0);
+    let current_module_info = ModuleInfo::from_id(hub.module_id(), hub.version().into())?;
+    let ibc_msg = ibc_client::ExecuteMsg::ModuleIbcAction {
+        host_chain: next_hop,
+        target_module: current_module_info,
+        msg: to_json_binary(&HubIbcMsg::IbcMint {
+            local_account_id: transfer.account_id,
+            collection: IbcCollection {
+                collection_id: transfer.collection_id,
+                name: collection.name,
+                symbol: collection.symbol,
+            },
+            recipient: transfer.recipient,
+            fallback_address: transfer.fallback_address,
+            tokens: transfer.tokens,
+            route,
+            on_arrival: transfer.on_arrival,
+        })?,
+        callback_info: Some(CallbackInfo {
+            id: TRANSFER_CALLBACK.to_string(),
+            msg: Some(to_json_binary(&HubIbcCallbackMsg::BurnTokens {
+                transfer_ids: transfer_ids.clone(),
+            })?),
+        }),
+    };
+
+    Ok((
+        wasm_execute(ibc_client, &ibc_msg, vec![])?.into(),
+        transfer_ids,
+    ))
+}
+
 pub fn transfer_callback(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     adapter: Hub,
     callback: IbcResponseMsg,
 ) -> HubResult {
-    // We burn the token that was successfully transfered (if so)
+    let msg: HubIbcCallbackMsg = from_json(callback.msg.ok_or(HubError::Transfer(
+        "There needs to be a message on callback".to_string(),
+    ))?)?;
+
+    let transfer_ids = match msg {
+        HubIbcCallbackMsg::BurnTokens { transfer_ids } => transfer_ids,
+        HubIbcCallbackMsg::BurnToken { token_id } => {
+            return legacy_transfer_callback(deps, adapter, callback.result, token_id)
+        }
+        HubIbcCallbackMsg::RouteResult {
+            chain,
+            collection_id,
+            token_ids,
+            error,
+        } => {
+            return route_result_callback(
+                deps,
+                adapter,
+                callback.result,
+                chain,
+                RouteResult {
+                    collection_id,
+                    token_ids,
+                    error,
+                },
+            )
+        }
+    };
 
-    let msg = match callback.result {
+    // The failure of an arrival hook is reported in the events of the acknowledgement
+    let mut arrival_hook_error = None;
+    let outcome = match callback.result {
         CallbackResult::Execute {
             initiator_msg: _,
             result,
-        } => {
-            result.map_err(HubError::Transfer)?;
+        } => result.map(|execution| {
+            arrival_hook_error = execution
+                .result
+                .iter()
+                .flat_map(|response| &response.events)
+                .flat_map(|event| &event.attributes)
+                .find(|attribute| attribute.key == ARRIVAL_HOOK_ERROR)
+                .map(|attribute| attribute.value.clone());
+        }),
+        CallbackResult::FatalError(error) => Err(error),
+        _ => unreachable!(),
+    };
 
-            let msg: HubIbcCallbackMsg = from_json(callback.msg.ok_or(HubError::Transfer(
-                "There needs to be a message on callback".to_string(),
-            ))?)?;
+    // Tokens that still need to be forwarded by the next hub stay escrowed until the end of their route
+    // Transfers that were already settled are skipped, so that a duplicate callback has no effect
+    let mut finalized_ids = vec![];
+    for transfer_id in transfer_ids {
+        let mut transfer = transfers().load(deps.storage, transfer_id)?;
+        if transfer.status != TransferStatus::Pending {
+            continue;
+        }
+        if arrival_hook_error.is_some() {
+            transfer.arrival_hook_error = arrival_hook_error.clone();
+            transfers().save(deps.storage, transfer_id, &transfer)?;
+        }
+        if outcome.is_ok() && transfer.route.len() > 1 {
+            transfer.status = TransferStatus::Forwarded;
+            transfers().save(deps.storage, transfer_id, &transfer)?;
+        } else {
+            finalized_ids.push(transfer_id);
+        }
+    }
 
-            let token_id = match msg {
-                HubIbcCallbackMsg::BurnToken { token_id } => token_id,
-            };
+    finalize_transfers(deps, &env, adapter, finalized_ids, outcome)
+}
+
+/// Settles the transfers started by the hub 0.0.1, whose token is escrowed in the collection it created
+/// 0.0.1 didn't record who sent the token, so a failed transfer leaves it in the hub as 0.0.1 did
+fn legacy_transfer_callback(
+    deps: DepsMut,
+    adapter: Hub,
+    result: CallbackResult,
+    token_id: String,
+) -> HubResult {
+    let outcome = match result {
+        CallbackResult::Execute {
+            initiator_msg: _,
+            result,
+        } => result.map(|_| ()),
+        CallbackResult::FatalError(error) => Err(error),
+        _ => unreachable!(),
+    };
+    if let Err(error) = outcome {
+        return Ok(adapter.custom_response(
+            "legacy-transfer-failed",
+            vec![("token_id", token_id), ("reason", error)],
+        ));
+    }
+
+    // Until the walk through the 0.0.1 collection goes past the token, its absence counts it as burned
+    if legacy_token_walked(deps.storage, &token_id)? {
+        update_supply(deps.storage, |supply| supply.burned += 1)?;
+    }
+    let nft = collection_address(deps.storage, DEFAULT_COLLECTION)?;
+    Ok(adapter
+        .custom_response("burn-token", vec![("token_id", token_id.clone())])
+        .add_message(wasm_execute(nft, &ExecuteMsg::Burn { token_id }, vec![])?))
+}
+
+/// Keeps the route results whose packet timed out, so that the admin can send them again with `ResendRouteResults`
+/// Results rejected by the previous hub are not kept, sending them again would fail the same way
+fn route_result_callback(
+    deps: DepsMut,
+    adapter: Hub,
+    result: CallbackResult,
+    chain: String,
+    route_result: RouteResult,
+) -> HubResult {
+    match result {
+        CallbackResult::FatalError(error) => {
+            UNDELIVERED_ROUTE_RESULTS.update(deps.storage, &chain, |results| -> StdResult<_> {
+                let mut results = results.unwrap_or_default();
+                results.push(route_result);
+                Ok(results)
+            })?;
+            Ok(adapter.custom_response(
+                "undelivered-route-result",
+                vec![("chain", chain), ("reason", error)],
+            ))
+        }
+        _ => Ok(adapter.custom_response("route-result", vec![("chain", chain)])),
+    }
+}
+
+/// Creates the IBC message that reports the result of forwarded tokens to the hub of `chain`, which forwarded them
+pub fn route_result_msg(
+    deps: Deps,
+    hub: &Hub,
+    chain: String,
+    route_result: RouteResult,
+) -> HubResult<CosmosMsg> {
+    let ibc_client = ibc_client_address(deps, hub)?;
+    let ibc_msg = ibc_client::ExecuteMsg::ModuleIbcAction {
+        host_chain: chain.clone(),
+        target_module: ModuleInfo::from_id(hub.module_id(), hub.version().into())?,
+        msg: to_json_binary(&HubIbcMsg::RouteResult {
+            collection_id: route_result.collection_id.clone(),
+            token_ids: route_result.token_ids.clone(),
+            error: route_result.error.clone(),
+        })?,
+        callback_info: Some(CallbackInfo {
+            id: TRANSFER_CALLBACK.to_string(),
+            msg: Some(to_json_binary(&HubIbcCallbackMsg::RouteResult {
+                chain,
+                collection_id: route_result.collection_id,
+                token_ids: route_result.token_ids,
+                error: route_result.error,
+            })?),
+        }),
+    };
+    Ok(wasm_execute(ibc_client, &ibc_msg, vec![])?.into())
+}
+
+/// Settles the transfers forwarded to `client_chain` once their route is over
+pub fn receive_route_result(
+    deps: DepsMut,
+    env: &Env,
+    hub: Hub,
+    client_chain: ChainName,
+    collection_id: String,
+    token_ids: Vec<String>,
+    error: Option<String>,
+) -> HubResult {
+    let transfer_ids = token_ids
+        .into_iter()
+        .map(|token_id| {
+            transfers()
+                .idx
+                .token
+                .prefix((collection_id.clone(), token_id.clone()))
+                .range(deps.storage, None, None, Order::Descending)
+                .find(|transfer| match transfer {
+                    // The result can be relayed before the acknowledgement of the forwarding packet
+                    Ok((_, transfer)) => {
+                        let forwarded = match transfer.status {
+                            TransferStatus::Forwarded => true,
+                            TransferStatus::Pending => transfer.route.len() > 1,
+                            _ => false,
+                        };
+                        forwarded && transfer.route.first() == Some(&client_chain.to_string())
+                    }
+                    Err(_) => true,
+                })
+                .transpose()?
+                .map(|(transfer_id, _)| transfer_id)
+                .ok_or(HubError::UnknownTransfer(token_id))
+        })
+        .collect::<HubResult<Vec<_>>>()?;
 
-            let burn_msg = wasm_execute(
-                NFT.load(deps.storage)?,
-                &ExecuteMsg::Burn { token_id },
-                vec![],
+    finalize_transfers(deps, env, hub, transfer_ids, error.map_or(Ok(()), Err))
+}
+
+/// Burns the escrowed tokens on success, gives them back to their owner on failure
+/// If the tokens were only forwarded by this hub, the result is sent back to the previous hub
+fn finalize_transfers(
+    deps: DepsMut,
+    env: &Env,
+    adapter: Hub,
+    transfer_ids: Vec<u64>,
+    outcome: Result<(), String>,
+) -> HubResult {
+    let transfer_mode = CONFIG.load(deps.storage)?.transfer_mode;
+    let chain_name = ChainName::from_chain_id(&env.block.chain_id);
+
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut route_results: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+    for transfer_id in transfer_ids {
+        let mut transfer = transfers().load(deps.storage, transfer_id)?;
+
+        // In escrow mode, tokens leaving their home chain stay escrowed here until they come back
+        let escrow = transfer_mode == TransferMode::Escrow
+            && token_home_chain(&transfer.token_id) == Some(chain_name.as_str());
+
+        let nft_msg = match (&outcome, &transfer.previous_hop) {
+            (Ok(()), _) => {
+                transfer.status = TransferStatus::Completed;
+                if escrow {
+                    None
+                } else {
+                    Some(ExecuteMsg::Burn {
+                        token_id: transfer.token_id.clone(),
+                    })
+                }
+            }
+            // The token will be given back by the origin chain, the forwarded copy is burned
+            (Err(error), Some(_)) => {
+                transfer.status = TransferStatus::Failed;
+                transfer.error = Some(error.clone());
+                if escrow {
+                    None
+                } else {
+                    Some(ExecuteMsg::Burn {
+                        token_id: transfer.token_id.clone(),
+                    })
+                }
+            }
+            (Err(error), None) => {
+                transfer.status = TransferStatus::Failed;
+                transfer.error = Some(error.clone());
+                if let Some(account_id) = &transfer.indexed_account {
+                    index_token(
+                        deps.storage,
+                        account_id,
+                        &transfer.collection_id,
+                        &transfer.token_id,
+                    )?;
+                }
+                Some(ExecuteMsg::TransferNft {
+                    recipient: transfer.sender.to_string(),
+                    token_id: transfer.token_id.clone(),
+                })
+            }
+        };
+        match nft_msg {
+            Some(nft_msg) => {
+                if let ExecuteMsg::Burn { .. } = nft_msg {
+                    update_supply(deps.storage, |supply| supply.burned += 1)?;
+                    ROYALTIES.remove(deps.storage, (&transfer.collection_id, &transfer.token_id));
+                }
+                let nft = collection_address(deps.storage, &transfer.collection_id)?;
+                msgs.push(wasm_execute(nft, &nft_msg, vec![])?.into())
+            }
+            None => {
+                ESCROWED_TOKENS.save(
+                    deps.storage,
+                    (&transfer.collection_id, &transfer.token_id),
+                    &Empty {},
+                )?;
+                update_supply(deps.storage, |supply| supply.escrowed += 1)?;
+            }
+        }
+
+        if let Some(previous_hop) = &transfer.previous_hop {
+            route_results
+                .entry((previous_hop.clone(), transfer.collection_id.clone()))
+                .or_default()
+                .push(transfer.token_id.clone());
+        }
+        transfers().save(deps.storage, transfer_id, &transfer)?;
+    }
+
+    // We report the result of the transfers to the hubs that forwarded the tokens to us
+    for ((previous_hop, collection_id), token_ids) in route_results {
+        msgs.push(route_result_msg(
+            deps.as_ref(),
+            &adapter,
+            previous_hop,
+            RouteResult {
+                collection_id,
+                token_ids,
+                error: outcome.clone().err(),
+            },
+        )?);
+    }
+
+    let response = match outcome {
+        Ok(()) => adapter.response("burn-token"),
+        Err(error) => adapter.custom_response("refund-token", vec![("reason", error)]),
+    };
+    Ok(response.add_messages(msgs))
+}
+
+/// Receives the forwarded tokens in the hub itself and sends them to the next chain of their route
+pub fn forward_tokens(
+    mut deps: DepsMut,
+    env: &Env,
+    hub: &Hub,
+    client_chain: ChainName,
+    collection: &IbcCollection,
+    transfer: OutboundTransfer,
+) -> HubResult<Response> {
+    let hub_address = env.contract.address.clone();
+    let receive_msgs = receive_tokens(
+        deps.branch(),
+        env,
+        hub,
+        collection,
+        &hub_address,
+        transfer.tokens.clone(),
+    )?;
+
+    let ibc_client = ibc_client_address(deps.as_ref(), hub)?;
+    let (forward_msg, transfer_ids) = send_tokens(deps.branch(), env, hub, ibc_client, transfer)?;
+
+    let transfer_ids = transfer_ids
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    Ok(hub
+        .custom_response(
+            "forward-tokens",
+            vec![
+                ("from", client_chain.to_string()),
+                ("transfer_ids", transfer_ids),
+            ],
+        )
+        .add_messages(receive_msgs)
+        .add_message(forward_msg))
+}
+
+/// Creates the messages that give the received tokens of the collection to `owner`
+/// If the collection doesn't exist on this chain yet, it is created with the same name and symbol as on the source chain
+/// Tokens escrowed on their home chain are released, the others are minted
+/// The royalty of the tokens is set again in the NFT contract, for the recipient account on this chain
+/// The tokens are checked before minting so that an already existing token id fails the whole packet with a clear error
+pub fn receive_tokens(
+    deps: DepsMut,
+    env: &Env,
+    hub: &Hub,
+    collection: &IbcCollection,
+    owner: &Addr,
+    tokens: Vec<IbcNft>,
+) -> HubResult<Vec<WasmMsg>> {
+    let collection_id = collection.collection_id.as_str();
+    let (instantiate_msg, nft) = match COLLECTIONS.may_load(deps.storage, collection_id)? {
+        Some(local_collection) => (None, local_collection.nft),
+        None => {
+            let (instantiate_msg, local_collection) = new_collection(
+                deps.as_ref(),
+                env,
+                collection_id,
+                collection.name.clone(),
+                collection.symbol.clone(),
+                false,
             )?;
-            Ok(burn_msg)
+            save_collection(deps.storage, collection_id, &local_collection)?;
+            (Some(instantiate_msg), local_collection.nft)
         }
-        CallbackResult::FatalError(error) => Err(HubError::Transfer(error)),
-        _ => unreachable!(),
-    }?;
+    };
+
+    let mut existing_tokens = vec![];
+    let mut received_ids = BTreeSet::new();
+    for token in &tokens {
+        let duplicate = !received_ids.insert(token.token_id.as_str());
+        if duplicate
+            || (!ESCROWED_TOKENS.has(deps.storage, (collection_id, &token.token_id))
+                && token_owner(deps.as_ref(), &nft, &token.token_id).is_some())
+        {
+            existing_tokens.push(token.token_id.clone());
+        }
+    }
+    if !existing_tokens.is_empty() {
+        return Err(HubError::TokenAlreadyExists {
+            token_ids: existing_tokens,
+        });
+    }
+
+    let mut nft_msgs = vec![];
+    for token in tokens {
+        let set_royalty_msg = match &token.royalty {
+            Some(royalty) => {
+                ROYALTIES.save(deps.storage, (collection_id, &token.token_id), royalty)?;
+                royalty_msg(deps.as_ref(), hub, &nft, &token.token_id, royalty)?
+            }
+            None => None,
+        };
+        if ESCROWED_TOKENS.has(deps.storage, (collection_id, &token.token_id)) {
+            ESCROWED_TOKENS.remove(deps.storage, (collection_id, &token.token_id));
+            update_supply(deps.storage, |supply| supply.escrowed -= 1)?;
+            // The metadata may have been modified while the token was away
+            nft_msgs.push(update_metadata_msg(
+                &nft,
+                &token.token_id,
+                token.token_uri,
+                token.extension,
+            )?);
+            let transfer_msg = ExecuteMsg::TransferNft {
+                recipient: owner.to_string(),
+                token_id: token.token_id,
+            };
+            nft_msgs.push(wasm_execute(&nft, &transfer_msg, vec![])?);
+        } else {
+            update_supply(deps.storage, |supply| supply.received += 1)?;
+            let mint_msg = ExecuteMsg::Mint(cw721_base::MintMsg {
+                token_id: token.token_id,
+                owner: owner.to_string(),
+                token_uri: token.token_uri,
+                extension: token.extension,
+            });
+            nft_msgs.push(wasm_execute(&nft, &mint_msg, vec![])?);
+        }
+        nft_msgs.extend(set_royalty_msg);
+    }
+
+    // The NFT contract of a new collection is instantiated before the tokens are minted in it
+    Ok(instantiate_msg.into_iter().chain(nft_msgs).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::{HUB_ID, HUB_VERSION};
+    use crate::state::{Collection, LegacyBackfill, LEGACY_BACKFILL, SUPPLY};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Binary, ContractResult, SystemResult, WasmQuery};
+    use cw721::OwnerOfResponse;
+    use cw721_metadata_onchain::QueryMsg;
+    use polytone::callbacks::ExecutionResponse;
+
+    fn ibc_nft(token_id: &str) -> IbcNft {
+        IbcNft {
+            token_id: token_id.to_string(),
+            token_uri: None,
+            extension: None,
+            royalty: None,
+        }
+    }
+
+    #[test]
+    fn existing_and_duplicate_tokens_are_rejected() {
+        let mut deps = mock_dependencies();
+        COLLECTIONS
+            .save(
+                deps.as_mut().storage,
+                DEFAULT_COLLECTION,
+                &Collection {
+                    nft: Addr::unchecked("nft"),
+                    name: "Cosmos Adventurers".to_string(),
+                    symbol: "IBC-CA".to_string(),
+                    label: "Cosmos Adventures NFT".to_string(),
+                    soulbound: false,
+                },
+            )
+            .unwrap();
+        // Only juno>0 is already minted on this chain
+        deps.querier.update_wasm(|query| {
+            let WasmQuery::Smart { msg, .. } = query else {
+                panic!("unexpected query {query:?}");
+            };
+            match from_json::<QueryMsg>(msg).unwrap() {
+                QueryMsg::OwnerOf { token_id, .. } if token_id == "juno>0" => {
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_json_binary(&OwnerOfResponse {
+                            owner: "owner".to_string(),
+                            approvals: vec![],
+                        })
+                        .unwrap(),
+                    ))
+                }
+                _ => SystemResult::Ok(ContractResult::Err("token not found".to_string())),
+            }
+        });
+        let hub = Hub::new(HUB_ID, HUB_VERSION, None);
+        let collection = IbcCollection {
+            collection_id: DEFAULT_COLLECTION.to_string(),
+            name: "Cosmos Adventurers".to_string(),
+            symbol: "IBC-CA".to_string(),
+        };
+        let owner = Addr::unchecked("owner");
+
+        let err = receive_tokens(
+            deps.as_mut(),
+            &mock_env(),
+            &hub,
+            &collection,
+            &owner,
+            vec![ibc_nft("juno>0"), ibc_nft("juno>1")],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            HubError::TokenAlreadyExists {
+                token_ids: vec!["juno>0".to_string()]
+            }
+        );
+
+        // The same packet delivered twice would mint the token twice
+        let err = receive_tokens(
+            deps.as_mut(),
+            &mock_env(),
+            &hub,
+            &collection,
+            &owner,
+            vec![ibc_nft("juno>1"), ibc_nft("juno>1")],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            HubError::TokenAlreadyExists {
+                token_ids: vec!["juno>1".to_string()]
+            }
+        );
+
+        let msgs = receive_tokens(
+            deps.as_mut(),
+            &mock_env(),
+            &hub,
+            &collection,
+            &owner,
+            vec![ibc_nft("juno>1")],
+        )
+        .unwrap();
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(SUPPLY.load(&deps.storage).unwrap().received, 1);
+    }
+
+    #[test]
+    fn timed_out_route_results_are_kept() {
+        let mut deps = mock_dependencies();
+        let callback = |result: CallbackResult| IbcResponseMsg {
+            id: TRANSFER_CALLBACK.to_string(),
+            msg: Some(
+                to_json_binary(&HubIbcCallbackMsg::RouteResult {
+                    chain: "juno".to_string(),
+                    collection_id: DEFAULT_COLLECTION.to_string(),
+                    token_ids: vec!["juno>0".to_string()],
+                    error: None,
+                })
+                .unwrap(),
+            ),
+            result,
+        };
+
+        // A result rejected by the previous hub would be rejected again
+        transfer_callback(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("ibc_client", &[]),
+            Hub::new(HUB_ID, HUB_VERSION, None),
+            callback(CallbackResult::Execute {
+                initiator_msg: Binary::default(),
+                result: Err("unknown transfer".to_string()),
+            }),
+        )
+        .unwrap();
+        assert!(!UNDELIVERED_ROUTE_RESULTS.has(&deps.storage, "juno"));
+
+        transfer_callback(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("ibc_client", &[]),
+            Hub::new(HUB_ID, HUB_VERSION, None),
+            callback(CallbackResult::FatalError("timeout".to_string())),
+        )
+        .unwrap();
+        assert_eq!(
+            UNDELIVERED_ROUTE_RESULTS
+                .load(&deps.storage, "juno")
+                .unwrap(),
+            vec![RouteResult {
+                collection_id: DEFAULT_COLLECTION.to_string(),
+                token_ids: vec!["juno>0".to_string()],
+                error: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn legacy_transfers_are_settled() {
+        let mut deps = mock_dependencies();
+        COLLECTIONS
+            .save(
+                deps.as_mut().storage,
+                DEFAULT_COLLECTION,
+                &Collection {
+                    nft: Addr::unchecked("nft"),
+                    name: "Cosmos Adventurers".to_string(),
+                    symbol: "IBC-CA".to_string(),
+                    label: "Cosmos Adventures NFT".to_string(),
+                    soulbound: false,
+                },
+            )
+            .unwrap();
+        LEGACY_BACKFILL
+            .save(
+                deps.as_mut().storage,
+                &LegacyBackfill {
+                    start_after: None,
+                    legacy_next_token_id: 1,
+                },
+            )
+            .unwrap();
+        let callback = |deps: DepsMut, result: CallbackResult| {
+            transfer_callback(
+                deps,
+                mock_env(),
+                mock_info("ibc_client", &[]),
+                Hub::new(HUB_ID, HUB_VERSION, None),
+                IbcResponseMsg {
+                    id: TRANSFER_CALLBACK.to_string(),
+                    msg: Some(
+                        to_json_binary(&HubIbcCallbackMsg::BurnToken {
+                            token_id: "juno>0".to_string(),
+                        })
+                        .unwrap(),
+                    ),
+                    result,
+                },
+            )
+            .unwrap()
+        };
+        let delivered = || CallbackResult::Execute {
+            initiator_msg: Binary::default(),
+            result: Ok(ExecutionResponse {
+                executed_by: "proxy".to_string(),
+                result: vec![],
+            }),
+        };
+
+        // 0.0.1 didn't record the sender, the token stays in the hub
+        let response = callback(
+            deps.as_mut(),
+            CallbackResult::FatalError("timeout".to_string()),
+        );
+        assert!(response.messages.is_empty());
+
+        // The walk will find the token missing and count it as burned
+        let response = callback(deps.as_mut(), delivered());
+        assert_eq!(response.messages.len(), 1);
+        assert_eq!(SUPPLY.may_load(&deps.storage).unwrap(), None);
 
-    Ok(adapter.response("burn-token").add_message(msg))
+        LEGACY_BACKFILL.remove(deps.as_mut().storage);
+        callback(deps.as_mut(), delivered());
+        assert_eq!(SUPPLY.load(&deps.storage).unwrap().burned, 1);
+    }
 }
@@ -4,6 +4,7 @@ mod handlers;
 pub mod helpers;
 pub mod ibc;
 pub mod msg;
+pub mod rate_limit;
 mod replies;
 pub mod state;
 #[cfg(feature = "interface")]
@@ -1,8 +1,17 @@
-use abstract_adapter::std::objects::AccountId;
+use std::fmt;
+
+use abstract_adapter::std::{app, objects::AccountId};
+use cosmwasm_schema::schemars::JsonSchema;
+use cosmwasm_schema::serde::de::{
+    self, value::MapAccessDeserializer, DeserializeSeed, IntoDeserializer, MapAccess, Visitor,
+};
+use cosmwasm_schema::serde::{Deserialize, Deserializer, Serialize};
 use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Binary, Coin, Decimal};
 
 use crate::contract::Hub;
-use cw721_metadata_onchain::{Extension, Metadata};
+use cw721::Cw721ReceiveMsg;
+use cw721_metadata_onchain::{Extension, Metadata, Trait};
 
 // This is used for type safety and re-exporting the contract endpoint structs.
 abstract_adapter::adapter_msg_types!(Hub, HubExecuteMsg, HubQueryMsg);
@@ -11,7 +20,31 @@ abstract_adapter::adapter_msg_types!(Hub, HubExecuteMsg, HubQueryMsg);
 #[cosmwasm_schema::cw_serde]
 pub struct HubInstantiateMsg {
     pub admin_account: AccountId,
+    /// Code id of the cosmos-adventures-nft contract, used by the collections the hub creates
     pub nft_code_id: u64,
+    /// Name of the collection created with the hub
+    pub nft_name: String,
+    /// Symbol of the collection created with the hub
+    pub nft_symbol: String,
+    /// Label of the NFT contract of the collection created with the hub
+    pub nft_label: String,
+    /// Existing NFT contract to use for the collection created with the hub, instead of instantiating one
+    /// The hub needs to be its minter, its name and symbol are used instead of `nft_name` and `nft_symbol`
+    /// Tokens with a royalty can only be minted in it if it handles the cosmos-adventures-nft extension
+    pub nft_address: Option<String>,
+    /// Defaults to [`TransferMode::BurnAndMint`]
+    pub transfer_mode: Option<TransferMode>,
+}
+
+/// How tokens minted on this chain leave it
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub enum TransferMode {
+    /// The token is burned here and a new copy is minted on the destination chain
+    #[default]
+    BurnAndMint,
+    /// The token is escrowed here while it lives on other chains and is given back when it returns
+    Escrow,
 }
 
 /// App execute messages
@@ -20,38 +53,467 @@ pub struct HubInstantiateMsg {
 #[cfg_attr(feature = "interface", impl_into(ExecuteMsg))]
 pub enum HubExecuteMsg {
     /// Transfer the NFT cross-chain
+    /// The NFT is sent to the remote account of the caller, unless a `recipient` is specified
+    /// If a `route` is specified, the NFT is forwarded through those chains before reaching `recipient_chain`
+    /// If the recipient account doesn't exist on `recipient_chain`, the NFT is given to `fallback_address`,
+    /// or held by the destination hub until the account claims it with `ClaimPending`
+    /// If `on_arrival` is specified, the destination hub calls that module of the recipient account once the NFT is received
     IbcTransfer {
         token_id: String,
+        /// Defaults to the collection created with the hub
+        collection_id: Option<String>,
         recipient_chain: String,
+        recipient: Option<Recipient>,
+        fallback_address: Option<String>,
+        route: Option<Vec<String>>,
+        on_arrival: Option<ArrivalHook>,
+    },
+
+    /// Transfer several NFTs cross-chain in a single IBC packet
+    /// The transfer is all-or-nothing: either all tokens are minted on the distant chain, or all are given back
+    IbcTransferBatch {
+        token_ids: Vec<String>,
+        /// Defaults to the collection created with the hub
+        collection_id: Option<String>,
+        recipient_chain: String,
+        recipient: Option<Recipient>,
+        fallback_address: Option<String>,
+        route: Option<Vec<String>>,
+        on_arrival: Option<ArrivalHook>,
+    },
+
+    /// Transfer NFTs to a chain running a standard ICS-721 contract
+    /// The NFTs are escrowed in the hub until they are sent back through the same channel
+    /// The channel id is used as the chain name for the chain settings, transfer fees and rate limits
+    Ics721Transfer {
+        token_ids: Vec<String>,
+        /// Defaults to the collection created with the hub
+        collection_id: Option<String>,
+        channel_id: String,
+        /// Address that receives the NFTs on the counterparty chain
+        receiver: String,
+        memo: Option<String>,
     },
 
     /// Mint a new lost token on this contract   
     /// This is an authorized endpoint that is only callable by another app in the same namespace
     Mint {
         module_id: String,
+        /// Defaults to the collection created with the hub
+        collection_id: Option<String>,
         token_uri: String,
         metadata: Metadata,
+        royalty: Option<Royalty>,
     },
 
-    /// Change the metadata of an NFT
+    /// Claim the NFTs received while the calling account didn't exist on this chain yet
+    ClaimPending {},
+
+    /// Set the fee paid to send tokens to `chain`, or remove it if `fee` is `None`
+    /// This is an admin endpoint that is only callable by the admin account
+    SetTransferFee { chain: String, fee: Option<Coin> },
+
+    /// Update the access settings of `chain`, unspecified fields are left unchanged
+    /// This is an admin endpoint that is only callable by the admin account
+    UpdateChainSettings {
+        chain: String,
+        allowlisted: Option<bool>,
+        denylisted: Option<bool>,
+        paused: Option<bool>,
+    },
+
+    /// Pause or resume all cross-chain transfers
+    /// This is an admin endpoint that is only callable by the admin account
+    SetPaused { paused: bool },
+
+    /// Set the limits on cross-chain transfers, or remove them if `rate_limits` is `None`
+    /// This is an admin endpoint that is only callable by the admin account
+    SetRateLimits { rate_limits: Option<RateLimits> },
+
+    /// Change the metadata of an NFT owned by the account
+    /// This is an authorized endpoint that is only callable by another app in the same namespace
+    /// The module can only change the fields it was allowed to by the admin with `SetMetadataPermissions`
+    /// The NFT contract of the collection needs to handle the cosmos-adventures-nft `UpdateMetadata` extension
+    ModifyMetadata {
+        module_id: String,
+        token_id: String,
+        /// Defaults to the collection created with the hub
+        collection_id: Option<String>,
+        metadata: MetadataPatch,
+    },
+
+    /// Burn an NFT owned by the account, to consume an item
     /// This is an authorized endpoint that is only callable by another app in the same namespace
-    ModifyMetadata {},
+    Burn {
+        module_id: String,
+        token_id: String,
+        /// Defaults to the collection created with the hub
+        collection_id: Option<String>,
+    },
+
+    /// Create a new collection with its own NFT contract
+    /// Collections are mirrored across chains by id, hubs that don't have the collection create it with the same name and symbol when its tokens arrive
+    /// This is an admin endpoint that is only callable by the admin account
+    /// Tokens of soulbound collections can't be transferred, neither cross-chain nor with cw721 transfers
+    /// Modules can still burn them and modify their metadata
+    CreateCollection {
+        collection_id: String,
+        name: String,
+        symbol: String,
+        #[serde(default)]
+        soulbound: bool,
+    },
+
+    /// Set the metadata fields a module can change with `ModifyMetadata`
+    /// This is an admin endpoint that is only callable by the admin account
+    SetMetadataPermissions {
+        module_id: String,
+        fields: Vec<MetadataField>,
+    },
+
+    /// Update the hub configuration, unspecified fields are left unchanged
+    /// This is an admin endpoint that is only callable by the admin account
+    UpdateConfig {
+        nft_code_id: Option<u64>,
+        transfer_mode: Option<TransferMode>,
+    },
+
+    /// Trust the modules of other namespaces, in addition to the hub namespace
+    /// Trusted modules can mint and change tokens, send IBC messages to the hub and be called on arrival
+    /// This is an admin endpoint that is only callable by the admin account
+    UpdateTrustedNamespaces {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+
+    /// Change the admin of the NFT contract of a collection, or clear it if `admin` is `None`
+    /// This is an admin endpoint that is only callable by the admin account
+    UpdateNftAdmin {
+        collection_id: String,
+        admin: Option<String>,
+    },
+
+    /// Migrate the NFT contract of a collection, the hub needs to be its admin
+    /// This is an admin endpoint that is only callable by the admin account
+    MigrateNft {
+        collection_id: String,
+        code_id: u64,
+        msg: Binary,
+    },
+
+    /// Propose a new admin account, it becomes the admin once it calls `AcceptAdmin`
+    /// This is an admin endpoint that is only callable by the admin account
+    ProposeAdmin { admin_account: AccountId },
+
+    /// Become the admin of the hub
+    /// This is only callable by the account proposed with `ProposeAdmin`
+    AcceptAdmin {},
+
+    /// Send again the route results whose packet to `chain` timed out
+    /// This is an admin endpoint that is only callable by the admin account
+    ResendRouteResults { chain: String },
+
+    /// Walk through the next tokens of the 0.0.1 collection, indexing the tokens held by accounts and correcting the supply
+    /// Needs to be called until the walk is over after migrating from 0.0.1
+    /// This is an admin endpoint that is only callable by the admin account
+    BackfillLegacyTokens { limit: Option<u32> },
+}
+
+/// Fields of the token [`Metadata`]
+#[cosmwasm_schema::cw_serde]
+pub enum MetadataField {
+    Image,
+    ImageData,
+    ExternalUrl,
+    Description,
+    Name,
+    Attributes,
+    BackgroundColor,
+    AnimationUrl,
+    YoutubeUrl,
+}
+
+/// New values of the token [`Metadata`] fields, unspecified fields are left unchanged
+#[cosmwasm_schema::cw_serde]
+#[derive(Default)]
+pub struct MetadataPatch {
+    pub image: Option<String>,
+    pub image_data: Option<String>,
+    pub external_url: Option<String>,
+    pub description: Option<String>,
+    pub name: Option<String>,
+    pub attributes: Option<Vec<Trait>>,
+    pub background_color: Option<String>,
+    pub animation_url: Option<String>,
+    pub youtube_url: Option<String>,
+}
+
+impl MetadataPatch {
+    /// Fields changed by the patch
+    pub fn fields(&self) -> Vec<MetadataField> {
+        [
+            (self.image.is_some(), MetadataField::Image),
+            (self.image_data.is_some(), MetadataField::ImageData),
+            (self.external_url.is_some(), MetadataField::ExternalUrl),
+            (self.description.is_some(), MetadataField::Description),
+            (self.name.is_some(), MetadataField::Name),
+            (self.attributes.is_some(), MetadataField::Attributes),
+            (
+                self.background_color.is_some(),
+                MetadataField::BackgroundColor,
+            ),
+            (self.animation_url.is_some(), MetadataField::AnimationUrl),
+            (self.youtube_url.is_some(), MetadataField::YoutubeUrl),
+        ]
+        .into_iter()
+        .filter_map(|(changed, field)| changed.then_some(field))
+        .collect()
+    }
+
+    pub fn apply(self, metadata: &mut Metadata) {
+        if self.image.is_some() {
+            metadata.image = self.image;
+        }
+        if self.image_data.is_some() {
+            metadata.image_data = self.image_data;
+        }
+        if self.external_url.is_some() {
+            metadata.external_url = self.external_url;
+        }
+        if self.description.is_some() {
+            metadata.description = self.description;
+        }
+        if self.name.is_some() {
+            metadata.name = self.name;
+        }
+        if self.attributes.is_some() {
+            metadata.attributes = self.attributes;
+        }
+        if self.background_color.is_some() {
+            metadata.background_color = self.background_color;
+        }
+        if self.animation_url.is_some() {
+            metadata.animation_url = self.animation_url;
+        }
+        if self.youtube_url.is_some() {
+            metadata.youtube_url = self.youtube_url;
+        }
+    }
+}
+
+/// Limits on cross-chain transfers, counted over fixed windows
+#[cosmwasm_schema::cw_serde]
+pub struct RateLimits {
+    /// Length of a window, in seconds
+    pub window: u64,
+    /// Maximum number of tokens an account can send in a window
+    pub max_tokens_per_account: Option<u32>,
+    /// Maximum number of tokens that can be sent to a destination chain in a window
+    pub max_tokens_per_chain: Option<u32>,
+}
+
+/// Messages accepted in the `msg` field of a cw721 `SendNft` to the hub
+/// This allows any holder of a token, including plain addresses, to send it cross-chain
+#[cosmwasm_schema::cw_serde]
+pub enum HubReceiveMsg {
+    /// Transfer the received NFT cross-chain to `recipient`
+    IbcTransfer {
+        recipient_chain: String,
+        recipient: Recipient,
+        fallback_address: Option<String>,
+        route: Option<Vec<String>>,
+        on_arrival: Option<ArrivalHook>,
+    },
 }
 
 #[cosmwasm_schema::cw_serde]
 pub enum HubIbcMsg {
-    /// Mint a new NFT on the chain from an IBC transfer
+    /// Mint new NFTs on the chain from an IBC transfer
+    /// If any of the mints fails, the whole packet fails
     IbcMint {
-        local_account_id: AccountId,
-        token_id: String,
-        token_uri: Option<String>,
-        extension: Extension,
+        /// Account that sent the tokens, `None` if they were sent by a plain address
+        local_account_id: Option<AccountId>,
+        /// Collection of the tokens, they are minted in the collection with the same id on the destination chain
+        collection: IbcCollection,
+        recipient: Option<Recipient>,
+        /// Address that receives the tokens if the recipient account doesn't exist on the destination chain
+        fallback_address: Option<String>,
+        tokens: Vec<IbcNft>,
+        /// Chains the tokens still need to be forwarded to, the last one being their destination
+        route: Vec<String>,
+        on_arrival: Option<ArrivalHook>,
+    },
+    /// Result of the end of the route of forwarded tokens, sent back to the hub that forwarded them
+    RouteResult {
+        collection_id: String,
+        token_ids: Vec<String>,
+        error: Option<String>,
     },
 }
 
+/// Owner of the tokens on the destination chain of a cross-chain transfer
+#[cosmwasm_schema::cw_serde]
+pub enum Recipient {
+    /// An Abstract Account, identified as seen from the destination chain
+    Account(AccountId),
+    /// A plain address on the destination chain
+    Address(String),
+}
+
+/// Sender of cross-chain transfers on this chain
+#[cosmwasm_schema::cw_serde]
+pub enum TransferSender {
+    /// An Abstract Account of this chain
+    Account(AccountId),
+    /// A plain address that sent its tokens with cw721 `SendNft`
+    Address(String),
+}
+
+/// App of the recipient account called by the destination hub once the tokens are received
+/// The app needs to be in a namespace trusted by the hub
+/// Failures of the hook don't fail the transfer, they are reported back to the source chain
+#[cosmwasm_schema::cw_serde]
+pub struct ArrivalHook {
+    pub module_id: String,
+    /// Payload given to the module in [`HubHookMsg::OnArrival`]
+    pub msg: Binary,
+}
+
+/// Message sent by the hub to the arrival hook apps
+/// Apps receive it as their module message
+#[cosmwasm_schema::cw_serde]
+pub enum HubHookMsg {
+    OnArrival {
+        token_ids: Vec<String>,
+        /// Chain the tokens come from
+        source_chain: String,
+        msg: Binary,
+    },
+}
+
+impl app::AppExecuteMsg for HubHookMsg {}
+
+/// Collection data carried across chains
+/// If the destination hub doesn't have the collection yet, it creates it with the same name and symbol
+#[cosmwasm_schema::cw_serde]
+pub struct IbcCollection {
+    pub collection_id: String,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// Token data carried across chains
+#[cosmwasm_schema::cw_serde]
+pub struct IbcNft {
+    pub token_id: String,
+    pub token_uri: Option<String>,
+    pub extension: Extension,
+    pub royalty: Option<Royalty>,
+}
+
+/// Royalty paid on the sales of a token, following cw2981
+/// The NFT contract answers the cw2981 queries, paying the proxy of the recipient account
+/// once that account exists on the chain the token lives on
+#[cosmwasm_schema::cw_serde]
+pub struct Royalty {
+    /// Account that receives the royalty, as identified on the chain the token lives on
+    pub recipient: AccountId,
+    /// Part of the sale price paid to the recipient, between 0 and 1
+    pub share: Decimal,
+}
+
 #[cosmwasm_schema::cw_serde]
 pub enum HubIbcCallbackMsg {
+    /// Burns the escrowed tokens on success, gives them back to the account on failure
+    BurnTokens { transfer_ids: Vec<u64> },
+    /// Callback of the transfers started by the hub 0.0.1, still in flight when it was migrated
     BurnToken { token_id: String },
+    /// Keeps the route result if it didn't reach the hub that forwarded the tokens
+    RouteResult {
+        chain: String,
+        collection_id: String,
+        token_ids: Vec<String>,
+        error: Option<String>,
+    },
+}
+
+/// cw721 hooks accepted by the hub
+#[cosmwasm_schema::cw_serde]
+pub enum Cw721HookMsg {
+    /// Sent by the NFT contract when a token is sent to the hub with `SendNft`
+    /// The `msg` field holds a [`HubReceiveMsg`]
+    ReceiveNft(Cw721ReceiveMsg),
+}
+
+/// Message accepted by the execute entry point of the hub
+/// cw721 hooks are recognized by their variant, any other message is parsed as an adapter message so that its parsing errors are kept
+#[derive(Serialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(crate = "cosmwasm_schema::serde", untagged)]
+#[schemars(crate = "cosmwasm_schema::schemars")]
+pub enum HubEntryExecuteMsg {
+    Cw721(Cw721HookMsg),
+    Adapter(ExecuteMsg),
+}
+
+impl<'de> Deserialize<'de> for HubEntryExecuteMsg {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(EntryExecuteMsgVisitor)
+    }
+}
+
+struct EntryExecuteMsgVisitor;
+
+impl<'de> Visitor<'de> for EntryExecuteMsgVisitor {
+    type Value = HubEntryExecuteMsg;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a cw721 hook or an adapter execute message")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let variant: String = map
+            .next_key()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        if variant == "receive_nft" {
+            return Ok(HubEntryExecuteMsg::Cw721(Cw721HookMsg::ReceiveNft(
+                map.next_value()?,
+            )));
+        }
+
+        // The variant was already read, it is given back to the adapter message
+        ExecuteMsg::deserialize(MapAccessDeserializer::new(ReplayedVariant {
+            variant: Some(variant),
+            map,
+        }))
+        .map(HubEntryExecuteMsg::Adapter)
+    }
+}
+
+/// Map whose first key was already consumed
+struct ReplayedVariant<A> {
+    variant: Option<String>,
+    map: A,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for ReplayedVariant<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.variant.take() {
+            Some(variant) => seed.deserialize(variant.into_deserializer()).map(Some),
+            None => self.map.next_key_seed(seed),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.map.next_value_seed(seed)
+    }
 }
 
 /// App query messages
@@ -64,18 +526,239 @@ pub enum HubQueryMsg {
     Config {},
     #[returns(NextTokenIdResponse)]
     NextTokenId {},
+    /// Cross-chain transfers initiated by an account or a plain address
+    #[returns(TransfersResponse)]
+    TransfersBySender {
+        sender: TransferSender,
+        status: Option<TransferStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Cross-chain transfers of a token
+    #[returns(TransfersResponse)]
+    TransfersByToken {
+        token_id: String,
+        /// Defaults to the collection created with the hub
+        collection_id: Option<String>,
+        status: Option<TransferStatus>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Whether a token id is already used in the NFT contract of a collection on this chain
+    #[returns(TokenExistsResponse)]
+    TokenExists {
+        token_id: String,
+        /// Defaults to the collection created with the hub
+        collection_id: Option<String>,
+    },
+    /// Collections managed by the hub
+    #[returns(CollectionsResponse)]
+    Collections {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Fee paid to send tokens to a destination chain
+    #[returns(TransferFeeResponse)]
+    TransferFee { chain: String },
+    /// Access settings of a chain and whether tokens can currently be exchanged with it
+    #[returns(ChainSettingsResponse)]
+    ChainSettings { chain: String },
+    /// Tokens held by the hub until the account claims them with `ClaimPending`
+    #[returns(ClaimableTokensResponse)]
+    ClaimableTokens {
+        account_id: AccountId,
+        start_after: Option<CollectionToken>,
+        limit: Option<u32>,
+    },
+    /// Metadata fields a module can change
+    #[returns(MetadataPermissionsResponse)]
+    MetadataPermissions { module_id: String },
+    /// Counters of the tokens that appeared and disappeared on this chain
+    #[returns(SupplyResponse)]
+    Supply {},
+    /// Tokens an account or a plain address can still send in the current rate limit window
+    /// If `chain` is specified, the remaining volume of that destination chain is returned as well
+    #[returns(RemainingQuotaResponse)]
+    RemainingQuota {
+        sender: TransferSender,
+        chain: Option<String>,
+    },
+    /// Tokens held by an account, with their metadata
+    /// Only the tokens the account received through the hub and still holds are listed
+    #[returns(AccountInventoryResponse)]
+    AccountInventory {
+        account_id: AccountId,
+        start_after: Option<CollectionToken>,
+        limit: Option<u32>,
+    },
+}
+
+/// Migrates the hub from any previous version
+/// Cross-chain transfers are paused after migrating from 0.0.1, until its tokens are walked through with `BackfillLegacyTokens`
+#[cosmwasm_schema::cw_serde]
+pub struct HubMigrateMsg {
+    /// Required when migrating from 0.0.1, which didn't store the admin account
+    pub admin_account: Option<AccountId>,
+    /// Code id of the cosmos-adventures-nft contract used by the collections created after the migration
+    /// Required when migrating from 0.0.1, whose NFT contract doesn't support soulbound collections and royalties
+    pub nft_code_id: Option<u64>,
+    /// NFT contracts to migrate along with the hub
+    #[serde(default)]
+    pub nft_migrations: Vec<NftMigration>,
 }
 
 #[cosmwasm_schema::cw_serde]
-pub enum HubMigrateMsg {}
+pub struct NftMigration {
+    pub collection_id: String,
+    pub code_id: u64,
+    pub msg: Binary,
+}
 
 #[cosmwasm_schema::cw_serde]
 pub struct ConfigResponse {
+    pub admin_account: AccountId,
+    /// Account proposed to become the admin, until it accepts
+    pub pending_admin_account: Option<AccountId>,
+    pub nft_code_id: u64,
+    /// Namespaces trusted in addition to the hub namespace
+    pub trusted_namespaces: Vec<String>,
+    /// NFT contract of the collection created with the hub
     pub nft: String,
+    pub nft_name: String,
+    pub nft_symbol: String,
+    pub nft_label: String,
     pub next_token_id: u64,
+    pub transfer_mode: TransferMode,
+    pub paused: bool,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct CollectionResponse {
+    pub collection_id: String,
+    pub nft: String,
+    pub name: String,
+    pub symbol: String,
+    pub label: String,
+    pub soulbound: bool,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct CollectionsResponse {
+    pub collections: Vec<CollectionResponse>,
 }
 
 #[cosmwasm_schema::cw_serde]
 pub struct NextTokenIdResponse {
     pub next_token_id: String,
 }
+
+#[cosmwasm_schema::cw_serde]
+pub struct ChainSettingsResponse {
+    pub allowlisted: bool,
+    pub denylisted: bool,
+    pub paused: bool,
+    /// Whether tokens can currently be sent to and received from the chain
+    pub open: bool,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct MetadataPermissionsResponse {
+    pub fields: Vec<MetadataField>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct SupplyResponse {
+    pub chain: String,
+    /// Tokens minted on this chain by the namespace modules
+    pub minted: u64,
+    /// Tokens minted on this chain by cross-chain transfers
+    pub received: u64,
+    /// Tokens burned on this chain after leaving it through a cross-chain transfer
+    pub burned: u64,
+    /// Tokens currently escrowed in the hub while they live on another chain
+    pub escrowed: u64,
+    /// Tokens burned on this chain by the namespace modules
+    pub consumed: u64,
+    /// Tokens that currently live on this chain
+    pub live: u64,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct ClaimableTokensResponse {
+    pub tokens: Vec<CollectionToken>,
+}
+
+/// Token of a collection, as listed across the collections of the hub
+#[cosmwasm_schema::cw_serde]
+pub struct CollectionToken {
+    pub collection_id: String,
+    pub token_id: String,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct InventoryTokenResponse {
+    pub token_id: String,
+    pub collection_id: String,
+    pub token_uri: Option<String>,
+    pub extension: Extension,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct AccountInventoryResponse {
+    pub tokens: Vec<InventoryTokenResponse>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct RemainingQuotaResponse {
+    pub account_remaining: Option<u32>,
+    pub chain_remaining: Option<u32>,
+    /// End of the current window, in seconds
+    pub window_end: Option<u64>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct TransferFeeResponse {
+    pub fee: Option<Coin>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct TokenExistsResponse {
+    pub exists: bool,
+    /// Current owner of the token, the hub itself if the token is escrowed
+    pub owner: Option<String>,
+    /// Whether the token can't leave its account
+    pub soulbound: bool,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub enum TransferStatus {
+    /// The token is escrowed and the packet is in transit
+    Pending,
+    /// The token reached the next chain and is being forwarded along its route
+    Forwarded,
+    /// The token was minted on the destination chain and burned here
+    Completed,
+    /// The destination chain errored, the token was given back to the account
+    Failed,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct TransferResponse {
+    pub transfer_id: u64,
+    pub token_id: String,
+    pub collection_id: String,
+    pub account_id: Option<AccountId>,
+    pub sender: String,
+    pub destination_chain: String,
+    pub route: Vec<String>,
+    pub previous_hop: Option<String>,
+    pub start_height: u64,
+    pub status: TransferStatus,
+    pub error: Option<String>,
+    pub arrival_hook_error: Option<String>,
+}
+
+#[cosmwasm_schema::cw_serde]
+pub struct TransfersResponse {
+    pub transfers: Vec<TransferResponse>,
+}
@@ -0,0 +1,120 @@
+use cosmwasm_std::{Deps, DepsMut, Env};
+
+use crate::{
+    contract::HubResult,
+    error::HubError,
+    msg::{RateLimits, RemainingQuotaResponse},
+    state::{WindowUsage, ACCOUNT_USAGE, CHAIN_USAGE, RATE_LIMITS},
+};
+
+/// Counts `amount` new transfers of `origin` to `destination_chain`
+/// `origin` is the account id, or the sender address for plain addresses
+/// Fails if this exceeds the account or chain limits of the current window
+pub fn consume_quota(
+    deps: DepsMut,
+    env: &Env,
+    origin: &str,
+    destination_chain: &str,
+    amount: u32,
+) -> HubResult<()> {
+    let Some(limits) = RATE_LIMITS.may_load(deps.storage)? else {
+        return Ok(());
+    };
+
+    if let Some(max_tokens) = limits.max_tokens_per_account {
+        let usage = current_usage(ACCOUNT_USAGE.may_load(deps.storage, origin)?, env, &limits);
+        let used = usage.count + amount;
+        if used > max_tokens {
+            return Err(HubError::AccountRateLimited {
+                max_tokens,
+                window: limits.window,
+            });
+        }
+        ACCOUNT_USAGE.save(
+            deps.storage,
+            origin,
+            &WindowUsage {
+                count: used,
+                ..usage
+            },
+        )?;
+    }
+
+    if let Some(max_tokens) = limits.max_tokens_per_chain {
+        let usage = current_usage(
+            CHAIN_USAGE.may_load(deps.storage, destination_chain)?,
+            env,
+            &limits,
+        );
+        let used = usage.count + amount;
+        if used > max_tokens {
+            return Err(HubError::ChainRateLimited {
+                chain: destination_chain.to_string(),
+                max_tokens,
+                window: limits.window,
+            });
+        }
+        CHAIN_USAGE.save(
+            deps.storage,
+            destination_chain,
+            &WindowUsage {
+                count: used,
+                ..usage
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Returns what is left of the account and chain quotas in the current window
+/// `origin` is the account id, or the sender address for plain addresses
+/// `None` means the corresponding limit is not set
+pub fn remaining_quota(
+    deps: Deps,
+    env: &Env,
+    origin: &str,
+    destination_chain: Option<String>,
+) -> HubResult<RemainingQuotaResponse> {
+    let Some(limits) = RATE_LIMITS.may_load(deps.storage)? else {
+        return Ok(RemainingQuotaResponse {
+            account_remaining: None,
+            chain_remaining: None,
+            window_end: None,
+        });
+    };
+
+    let account_usage = current_usage(ACCOUNT_USAGE.may_load(deps.storage, origin)?, env, &limits);
+    let account_remaining = limits
+        .max_tokens_per_account
+        .map(|max_tokens| max_tokens.saturating_sub(account_usage.count));
+
+    let chain_remaining = match (limits.max_tokens_per_chain, destination_chain) {
+        (Some(max_tokens), Some(chain)) => {
+            let chain_usage =
+                current_usage(CHAIN_USAGE.may_load(deps.storage, &chain)?, env, &limits);
+            Some(max_tokens.saturating_sub(chain_usage.count))
+        }
+        _ => None,
+    };
+
+    Ok(RemainingQuotaResponse {
+        account_remaining,
+        chain_remaining,
+        window_end: Some(account_usage.window_start + limits.window),
+    })
+}
+
+/// Usage is counted over fixed windows of `limits.window` seconds
+/// A usage recorded in a previous window is reset
+fn current_usage(usage: Option<WindowUsage>, env: &Env, limits: &RateLimits) -> WindowUsage {
+    let now = env.block.time.seconds();
+    let window_start = now - now % limits.window;
+    match usage {
+        Some(usage) if usage.window_start == window_start => usage,
+        _ => WindowUsage {
+            window_start,
+            count: 0,
+        },
+    }
+}
@@ -0,0 +1,18 @@
+use abstract_sdk::AbstractResponse;
+use cosmwasm_std::{DepsMut, Env, Reply, SubMsgResult};
+
+use crate::contract::{Hub, HubResult};
+
+/// Attribute that reports the failure of an arrival hook to the chain that sent the tokens
+pub const ARRIVAL_HOOK_ERROR: &str = "arrival_hook_error";
+
+/// The hook failed but the tokens are still received
+/// The error is added to the response events, which are part of the acknowledgement sent back to the source chain
+pub fn arrival_hook_reply(_deps: DepsMut, _env: Env, hub: Hub, reply: Reply) -> HubResult {
+    let error = match reply.result {
+        SubMsgResult::Err(error) => error,
+        SubMsgResult::Ok(_) => unreachable!(),
+    };
+
+    Ok(hub.custom_response("arrival-hook", vec![(ARRIVAL_HOOK_ERROR, error)]))
+}
@@ -1 +1,5 @@
+pub mod arrival_hook;
 
+pub use arrival_hook::arrival_hook_reply;
+
+pub const ARRIVAL_HOOK_REPLY_ID: u64 = 1;
@@ -1,17 +1,236 @@
 use abstract_adapter::std::objects::AccountId;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Coin, Empty};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+
+use crate::msg::{MetadataField, RateLimits, Royalty, TransferMode, TransferStatus};
 
 #[cosmwasm_schema::cw_serde]
 pub struct Config {
+    /// Account that manages the hub settings and receives the transfer fees
+    pub admin_account: AccountId,
+    /// Code id of the NFT contracts of the collections
+    pub nft_code_id: u64,
     pub next_token_id: u64,
+    pub transfer_mode: TransferMode,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const NFT: Item<Addr> = Item::new("nft");
+/// Account proposed to become the admin, until it accepts
+pub const PENDING_ADMIN: Item<AccountId> = Item::new("pending_admin");
+/// Namespaces whose modules are trusted in addition to the hub namespace
+pub const TRUSTED_NAMESPACES: Map<&str, Empty> = Map::new("trusted_namespaces");
+/// Id of the collection created with the hub
+pub const DEFAULT_COLLECTION: &str = "default";
+/// Collections managed by the hub, by collection id
+/// A collection has the same id on every chain, so that its tokens are minted in the matching collection
+pub const COLLECTIONS: Map<&str, Collection> = Map::new("collections");
+/// Collection ids, by address of their NFT contract
+pub const COLLECTION_IDS: Map<&Addr, String> = Map::new("collection_ids");
+
+#[cw_serde]
+pub struct Collection {
+    /// NFT contract of the collection
+    pub nft: Addr,
+    pub name: String,
+    pub symbol: String,
+    /// Label of the NFT contract
+    pub label: String,
+    /// Tokens of soulbound collections can't leave the account they were minted to
+    pub soulbound: bool,
+}
+/// Counters of the tokens that appeared and disappeared on this chain
+pub const SUPPLY: Item<Supply> = Item::new("supply");
+/// Tokens minted on this chain that are escrowed in the hub while they live on another chain, by collection id and token id
+pub const ESCROWED_TOKENS: Map<(&str, &str), Empty> = Map::new("escrowed_tokens");
+/// Royalties of the tokens living on this chain, by collection id and token id
+pub const ROYALTIES: Map<(&str, &str), Royalty> = Map::new("royalties");
+/// Tokens held by the hub for accounts that didn't exist on this chain when they received them, by account, collection id and token id
+pub const PENDING_CLAIMS: Map<(&AccountId, &str, &str), Empty> = Map::new("pending_claims");
+/// Metadata fields each module can change, by module id
+pub const METADATA_PERMISSIONS: Map<&str, Vec<MetadataField>> = Map::new("metadata_permissions");
+/// Fee paid by accounts to send tokens to a destination chain, by destination chain
+pub const TRANSFER_FEES: Map<&str, Coin> = Map::new("transfer_fees");
+/// When set, no token can leave or enter the hub through cross-chain transfers
+pub const PAUSED: Item<bool> = Item::new("paused");
+/// Access settings of the chains the hub exchanges tokens with, by chain name
+pub const CHAIN_SETTINGS: Map<&str, ChainSettings> = Map::new("chain_settings");
+
+/// Limits on the number of cross-chain transfers, no limit applies when unset
+pub const RATE_LIMITS: Item<RateLimits> = Item::new("rate_limits");
+/// Tokens sent by each account or plain address in the current rate limit window
+pub const ACCOUNT_USAGE: Map<&str, WindowUsage> = Map::new("account_usage");
+/// Tokens sent to each destination chain in the current rate limit window
+pub const CHAIN_USAGE: Map<&str, WindowUsage> = Map::new("chain_usage");
+
+#[cw_serde]
+pub struct WindowUsage {
+    /// Start of the window, in seconds
+    pub window_start: u64,
+    pub count: u32,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct ChainSettings {
+    /// If any chain is allowlisted, only allowlisted chains can be reached
+    pub allowlisted: bool,
+    pub denylisted: bool,
+    pub paused: bool,
+}
+
+/// Summed over all chains, the live tokens equal the tokens minted by all the hubs
+#[cw_serde]
+#[derive(Default)]
+pub struct Supply {
+    /// Tokens minted on this chain by the namespace modules
+    pub minted: u64,
+    /// Tokens minted on this chain by cross-chain transfers
+    pub received: u64,
+    /// Tokens burned on this chain after leaving it through a cross-chain transfer
+    pub burned: u64,
+    /// Tokens currently escrowed in the hub while they live on another chain
+    pub escrowed: u64,
+    /// Tokens burned on this chain by the namespace modules
+    pub consumed: u64,
+}
+
+impl Supply {
+    /// Tokens that currently live on this chain
+    pub fn live(&self) -> u64 {
+        (self.minted + self.received).saturating_sub(self.burned + self.escrowed + self.consumed)
+    }
+}
 
 #[cw_serde]
 pub struct Account {
     pub account_id: AccountId,
 }
+
+/// A cross-chain transfer initiated on this hub
+#[cw_serde]
+pub struct Transfer {
+    pub token_id: String,
+    pub collection_id: String,
+    /// Account the token was escrowed from, `None` if it was sent by a plain address
+    pub account_id: Option<AccountId>,
+    /// Address the token was escrowed from, it is given back to it if the transfer fails
+    pub sender: Addr,
+    /// Account whose inventory held the token, it is indexed under it again if the transfer fails
+    pub indexed_account: Option<AccountId>,
+    pub destination_chain: String,
+    /// Chains the token is sent through, starting with the chain it is sent to
+    pub route: Vec<String>,
+    /// Chain the token was forwarded from, if it didn't originate here
+    pub previous_hop: Option<String>,
+    pub start_height: u64,
+    pub status: TransferStatus,
+    /// Error returned by the destination chain when the transfer failed
+    pub error: Option<String>,
+    /// Error of the module called on arrival, the transfer itself succeeded
+    pub arrival_hook_error: Option<String>,
+}
+
+pub struct TransferIndexes<'a> {
+    pub account: MultiIndex<'a, String, Transfer, u64>,
+    pub token: MultiIndex<'a, (String, String), Transfer, u64>,
+}
+
+impl<'a> IndexList<Transfer> for TransferIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Transfer>> + '_> {
+        let v: Vec<&dyn Index<Transfer>> = vec![&self.account, &self.token];
+        Box::new(v.into_iter())
+    }
+}
+
+/// All cross-chain transfers, by transfer id, indexed by account and by collection id and token id
+/// Transfers sent by plain addresses are indexed by their sender address instead of their account
+pub fn transfers<'a>() -> IndexedMap<'a, u64, Transfer, TransferIndexes<'a>> {
+    let indexes = TransferIndexes {
+        account: MultiIndex::new(
+            |_pk, transfer| transfer_origin(transfer.account_id.as_ref(), &transfer.sender),
+            "transfers",
+            "transfers__account",
+        ),
+        token: MultiIndex::new(
+            |_pk, transfer| (transfer.collection_id.clone(), transfer.token_id.clone()),
+            "transfers",
+            "transfers__token",
+        ),
+    };
+    IndexedMap::new("transfers", indexes)
+}
+
+pub const NEXT_TRANSFER_ID: Item<u64> = Item::new("next_transfer_id");
+
+/// Route results whose packet timed out, by chain of the hub that forwarded the tokens, until the admin sends them again
+pub const UNDELIVERED_ROUTE_RESULTS: Map<&str, Vec<RouteResult>> =
+    Map::new("undelivered_route_results");
+
+/// Result of the end of the route of forwarded tokens
+#[cw_serde]
+pub struct RouteResult {
+    pub collection_id: String,
+    pub token_ids: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// A token held by an account, as tracked by the hub
+#[cw_serde]
+pub struct AccountToken {
+    pub account_id: AccountId,
+}
+
+pub struct AccountTokenIndexes<'a> {
+    pub account: MultiIndex<'a, String, AccountToken, (String, String)>,
+}
+
+impl<'a> IndexList<AccountToken> for AccountTokenIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<AccountToken>> + '_> {
+        let v: Vec<&dyn Index<AccountToken>> = vec![&self.account];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Tokens held by accounts, by collection id and token id, indexed by account
+/// Only the tokens the hub gives to accounts are tracked, they leave the index when they are escrowed or burned
+pub fn account_tokens<'a>(
+) -> IndexedMap<'a, (&'a str, &'a str), AccountToken, AccountTokenIndexes<'a>> {
+    let indexes = AccountTokenIndexes {
+        account: MultiIndex::new(
+            |_pk, token| token.account_id.to_string(),
+            "account_tokens",
+            "account_tokens__account",
+        ),
+    };
+    IndexedMap::new("account_tokens", indexes)
+}
+
+/// Channels opened with a counterparty ICS-721 contract
+pub const ICS721_CHANNELS: Map<&str, Empty> = Map::new("ics721_channels");
+/// Tokens escrowed in the hub while they live on an ICS-721 chain, by collection id and token id
+pub const ICS721_ESCROW: Map<(&str, &str), Ics721Escrow> = Map::new("ics721_escrow");
+
+#[cw_serde]
+pub struct Ics721Escrow {
+    /// Channel the token left on, it can only come back through it
+    pub channel_id: String,
+    /// Account the token was sent from, it is indexed under it again if the packet fails
+    pub account_id: AccountId,
+}
+
+/// Progress of the walk through the tokens of the 0.0.1 collection, removed once every token was walked through
+pub const LEGACY_BACKFILL: Item<LegacyBackfill> = Item::new("legacy_backfill");
+
+#[cw_serde]
+pub struct LegacyBackfill {
+    /// Last token id walked through
+    pub start_after: Option<String>,
+    /// Next token id of the 0.0.1 hub, the tokens of this chain from this id on were minted after the migration
+    pub legacy_next_token_id: u64,
+}
+
+/// Key identifying who initiated a transfer: the account id, or the address for plain addresses
+pub fn transfer_origin(account_id: Option<&AccountId>, sender: &Addr) -> String {
+    account_id.map_or_else(|| sender.to_string(), ToString::to_string)
+}
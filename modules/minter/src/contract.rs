@@ -1,12 +1,11 @@
 use crate::ibc;
-use crate::msg::MinterMigrateMsg;
 use crate::{
     error::MinterError,
     handlers,
     msg::{MinterExecuteMsg, MinterInstantiateMsg, MinterQueryMsg},
 };
 use abstract_adapter::AdapterContract;
-use cosmwasm_std::Response;
+use cosmwasm_std::{Empty, Response};
 
 /// The version of your app
 pub const MINTER_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -17,13 +16,9 @@ pub const MINTER_ID: &str = "cosmos-adventures:cross-chain-mint";
 pub type MinterResult<T = Response> = Result<T, MinterError>;
 
 /// The type of the app that is used to build your app and access the Abstract SDK features.
-pub type Minter = AdapterContract<
-    MinterError,
-    MinterInstantiateMsg,
-    MinterExecuteMsg,
-    MinterQueryMsg,
-    MinterMigrateMsg,
->;
+/// Adapters have no migrate handler, the minter migrations are handled by its own entry point
+pub type Minter =
+    AdapterContract<MinterError, MinterInstantiateMsg, MinterExecuteMsg, MinterQueryMsg, Empty>;
 
 const MINTER: Minter = Minter::new(MINTER_ID, MINTER_VERSION, None)
     .with_instantiate(handlers::instantiate_handler)
@@ -37,10 +32,20 @@ const MINTER: Minter = Minter::new(MINTER_ID, MINTER_VERSION, None)
 #[cfg(feature = "export")]
 abstract_adapter::export_endpoints!(MINTER, Minter);
 
+#[cfg(feature = "export")]
+#[cosmwasm_std::entry_point]
+pub fn migrate(
+    deps: cosmwasm_std::DepsMut,
+    env: cosmwasm_std::Env,
+    msg: crate::msg::MinterMigrateMsg,
+) -> MinterResult {
+    handlers::migrate_handler(deps, env, MINTER, msg)
+}
+
 #[cfg(feature = "interface")]
 pub mod interface {
     use crate::msg::MinterInstantiateMsg;
-    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+    use crate::msg::{ExecuteMsg, InstantiateMsg, MinterMigrateMsg, QueryMsg};
     use abstract_interface::AdapterDeployer;
     use abstract_interface::RegisteredModule;
     use abstract_sdk::features::ModuleIdentification;
@@ -51,7 +56,7 @@ pub mod interface {
 
     use super::MINTER;
 
-    #[interface(InstantiateMsg, ExecuteMsg, QueryMsg, Empty)]
+    #[interface(InstantiateMsg, ExecuteMsg, QueryMsg, MinterMigrateMsg)]
     pub struct CosmosAdventuresMinter<Chain>;
 
     // Implement deployer trait
@@ -65,7 +70,8 @@ pub mod interface {
                     crate::contract::instantiate,
                     crate::contract::query,
                 )
-                .with_reply(crate::contract::reply),
+                .with_reply(crate::contract::reply)
+                .with_migrate(crate::contract::migrate),
             )
         }
         fn wasm(_chain: &ChainInfoOwned) -> WasmPath {
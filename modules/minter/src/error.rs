@@ -32,6 +32,9 @@ pub enum MinterError {
     #[error("{0}")]
     Instantiate2Address(#[from] Instantiate2AddressError),
 
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -43,4 +46,17 @@ pub enum MinterError {
 
     #[error("You have minted too muchtokens already. Limit: {0}")]
     TooMuchMinted(usize),
+
+    #[error("Can't migrate {contract} from version {from} to {to}")]
+    CannotMigrate {
+        contract: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl From<semver::Error> for MinterError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
 }
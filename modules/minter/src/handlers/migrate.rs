@@ -0,0 +1,65 @@
+use crate::contract::{Minter, MinterResult, MINTER_ID, MINTER_VERSION};
+use crate::error::MinterError;
+use crate::msg::MinterMigrateMsg;
+use abstract_sdk::AbstractResponse;
+use cosmwasm_std::{DepsMut, Env};
+use semver::Version;
+
+/// Adapters are usually replaced by a new version rather than migrated, but the deployed minter holds the config and the
+/// mint counts of the accounts, so it is upgraded in place to keep them
+/// The version check refuses downgrades and migrating another contract with this code
+pub fn migrate_handler(
+    deps: DepsMut,
+    _env: Env,
+    minter: Minter,
+    _msg: MinterMigrateMsg,
+) -> MinterResult {
+    let stored = cw2::get_contract_version(deps.storage)?;
+    let from: Version = stored.version.parse()?;
+    let to: Version = MINTER_VERSION.parse()?;
+    if stored.contract != MINTER_ID || from >= to {
+        return Err(MinterError::CannotMigrate {
+            contract: stored.contract,
+            from: stored.version,
+            to: MINTER_VERSION.to_string(),
+        });
+    }
+
+    // The minter state didn't change since 0.1.0, new steps are added here when it does
+
+    cw2::set_contract_version(deps.storage, MINTER_ID, MINTER_VERSION)?;
+
+    Ok(minter.custom_response(
+        "migrate",
+        vec![("from", stored.version), ("to", MINTER_VERSION.to_string())],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    #[test]
+    fn migrates_from_previous_versions_only() {
+        let mut deps = mock_dependencies();
+        let minter = || Minter::new(MINTER_ID, MINTER_VERSION, None);
+
+        cw2::set_contract_version(deps.as_mut().storage, "other:module", "0.1.0").unwrap();
+        let err =
+            migrate_handler(deps.as_mut(), mock_env(), minter(), MinterMigrateMsg {}).unwrap_err();
+        assert!(matches!(err, MinterError::CannotMigrate { .. }));
+
+        cw2::set_contract_version(deps.as_mut().storage, MINTER_ID, "0.1.0").unwrap();
+        migrate_handler(deps.as_mut(), mock_env(), minter(), MinterMigrateMsg {}).unwrap();
+        assert_eq!(
+            cw2::get_contract_version(&deps.storage).unwrap().version,
+            MINTER_VERSION
+        );
+
+        // The minter can't be migrated again to the same version
+        let err =
+            migrate_handler(deps.as_mut(), mock_env(), minter(), MinterMigrateMsg {}).unwrap_err();
+        assert!(matches!(err, MinterError::CannotMigrate { .. }));
+    }
+}
@@ -78,8 +78,10 @@ fn internal_ibc_mint_token(
                 proxy_address: Some(resolved_account.proxy.to_string()),
                 request: HubExecuteMsg::Mint {
                     module_id: MINTER_ID.to_string(),
+                    collection_id: None,
                     token_uri: config.token_uri_base,
                     metadata: config.metadata_base,
+                    royalty: None,
                 },
             },
         ),
@@ -101,7 +103,12 @@ fn internal_ibc_mint_token(
                         proxy_address: Some(resolved_account.proxy.to_string()),
                         request: HubExecuteMsg::IbcTransfer {
                             token_id: next_token_id.next_token_id,
+                            collection_id: None,
                             recipient_chain: client_chain.to_string(),
+                            recipient: None,
+                            fallback_address: None,
+                            route: None,
+                            on_arrival: None,
                         },
                     },
                 ),
@@ -37,8 +37,9 @@ pub enum MinterQueryMsg {
     Config {},
 }
 
+/// Migrates the minter from any previous version
 #[cosmwasm_schema::cw_serde]
-pub enum MinterMigrateMsg {}
+pub struct MinterMigrateMsg {}
 
 #[cosmwasm_schema::cw_serde]
 pub struct ConfigResponse {
@@ -2,7 +2,11 @@ use abstract_client::AbstractClient;
 use abstract_client::Account;
 
 use abstract_client::Namespace;
-use cosmos_adventures_hub::{contract::HUB_ID, msg::HubInstantiateMsg, *};
+use cosmos_adventures_hub::{
+    contract::HUB_ID,
+    msg::{HubInstantiateMsg, TransferMode},
+    *,
+};
 use cosmwasm_std::coin;
 use cw721_metadata_onchain::Metadata;
 // Use prelude to get all the necessary imports
@@ -34,6 +38,14 @@ pub fn nft_metadata() -> (String, Metadata) {
 /// Set up the test environment with an Account that has the App installed
 #[allow(clippy::type_complexity)]
 pub fn setup_adapters<Chain: CwEnv>(chain: Chain) -> anyhow::Result<AbstractClient<Chain>> {
+    setup_adapters_with_transfer_mode(chain, None)
+}
+
+/// Set up the test environment, with the hub published in `transfer_mode`
+pub fn setup_adapters_with_transfer_mode<Chain: CwEnv>(
+    chain: Chain,
+    transfer_mode: Option<TransferMode>,
+) -> anyhow::Result<AbstractClient<Chain>> {
     // Deploy an NFT contract
     let nft = Cw721::new("nft_metadata", chain.clone());
     nft.upload()?;
@@ -52,7 +64,12 @@ pub fn setup_adapters<Chain: CwEnv>(chain: Chain) -> anyhow::Result<AbstractClie
     // We publish the HUB
     publisher.publish_adapter::<_, CosmosAdventuresHub<_>>(HubInstantiateMsg {
         nft_code_id: nft.code_id()?,
+        nft_name: "Cosmos Adventurers".to_string(),
+        nft_symbol: "IBC-CA".to_string(),
+        nft_label: "Cosmos Adventures NFT".to_string(),
+        nft_address: None,
         admin_account: publisher.account().id()?,
+        transfer_mode,
     })?;
 
     // We publish the Minter
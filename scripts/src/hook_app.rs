@@ -0,0 +1,110 @@
+//! App that records the arrival hooks the hub sends to it
+use abstract_app::std::app::{self, AppQueryMsg};
+use abstract_app::{AppContract, AppError};
+use abstract_interface::{AppDeployer, DependencyCreation, RegisteredModule};
+use abstract_sdk::base::{ExecuteEndpoint, InstantiateEndpoint, QueryEndpoint};
+use abstract_sdk::features::ModuleIdentification;
+use abstract_sdk::AbstractResponse;
+use cosmos_adventures_hub::msg::HubHookMsg;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response};
+use cw_orch::contract::Contract;
+use cw_orch::interface;
+use cw_orch::prelude::*;
+use cw_storage_plus::Item;
+
+pub const HOOK_APP_ID: &str = "cosmos-adventures:hook-app";
+pub const HOOK_APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Last hook received by the app
+const LAST_HOOK: Item<HubHookMsg> = Item::new("last_hook");
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum HookAppQueryMsg {
+    #[returns(Option<HubHookMsg>)]
+    LastHook {},
+}
+
+impl AppQueryMsg for HookAppQueryMsg {}
+
+pub type HookApp = AppContract<AppError, Empty, HubHookMsg, HookAppQueryMsg, Empty>;
+
+const HOOK_APP: HookApp = HookApp::new(HOOK_APP_ID, HOOK_APP_VERSION, None)
+    .with_instantiate(|_, _, _, _, _| Ok(Response::new()))
+    .with_execute(|deps, _, _, app, msg| {
+        LAST_HOOK.save(deps.storage, &msg)?;
+        Ok(app.response("on-arrival"))
+    })
+    .with_query(|deps, _, _, msg| match msg {
+        HookAppQueryMsg::LastHook {} => Ok(to_json_binary(&LAST_HOOK.may_load(deps.storage)?)?),
+    });
+
+fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: app::InstantiateMsg<Empty>,
+) -> Result<Response, AppError> {
+    HOOK_APP.instantiate(deps, env, info, msg)
+}
+
+fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: app::ExecuteMsg<HubHookMsg>,
+) -> Result<Response, AppError> {
+    HOOK_APP.execute(deps, env, info, msg)
+}
+
+fn query(deps: Deps, env: Env, msg: app::QueryMsg<HookAppQueryMsg>) -> Result<Binary, AppError> {
+    HOOK_APP.query(deps, env, msg)
+}
+
+#[interface(
+    app::InstantiateMsg<Empty>,
+    app::ExecuteMsg<HubHookMsg>,
+    app::QueryMsg<HookAppQueryMsg>,
+    app::MigrateMsg<Empty>
+)]
+pub struct HookAppInterface<Chain>;
+
+impl<Chain: CwEnv> AppDeployer<Chain> for HookAppInterface<Chain> {}
+
+impl<Chain: CwEnv> Uploadable for HookAppInterface<Chain> {
+    fn wrapper() -> <Mock as TxHandler>::ContractSource {
+        Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+    }
+}
+
+impl<Chain: CwEnv> RegisteredModule for HookAppInterface<Chain> {
+    type InitMsg = Empty;
+
+    fn module_id<'a>() -> &'a str {
+        HOOK_APP.module_id()
+    }
+
+    fn module_version<'a>() -> &'a str {
+        HOOK_APP.version()
+    }
+}
+
+impl<Chain: CwEnv> From<Contract<Chain>> for HookAppInterface<Chain> {
+    fn from(contract: Contract<Chain>) -> Self {
+        Self(contract)
+    }
+}
+
+impl<Chain: CwEnv> DependencyCreation for HookAppInterface<Chain> {
+    type DependenciesConfig = Empty;
+
+    fn dependency_install_configs(
+        _configuration: Self::DependenciesConfig,
+    ) -> Result<
+        Vec<abstract_app::std::manager::ModuleInstallConfig>,
+        abstract_interface::AbstractInterfaceError,
+    > {
+        Ok(vec![])
+    }
+}
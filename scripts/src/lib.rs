@@ -1,5 +1,6 @@
 pub mod account;
 pub mod adapters;
+pub mod hook_app;
 pub mod ibc;
 pub mod nft;
 
@@ -1,3 +1,4 @@
+use cosmos_adventures_nft::msg::{Cw2981QueryMsg, NftExecuteMsg, RoyaltiesInfoResponse};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::Binary;
@@ -53,8 +54,8 @@ pub enum ExecuteMsg {
     /// Burn an NFT the sender has access to
     Burn { token_id: String },
 
-    /// Extension msg
-    Extension { msg: Extension },
+    /// Extensions of the cosmos adventures NFT contract, can only be called by the contract minter
+    Extension { msg: NftExecuteMsg },
 }
 
 #[cw_serde]
@@ -138,21 +139,29 @@ pub enum QueryMsg {
     #[returns(MinterResponse)]
     Minter {},
 
-    /// Extension query
-    #[returns(Empty)]
-    Extension { msg: Extension },
+    /// cw2981 queries
+    #[returns(RoyaltiesInfoResponse)]
+    Extension { msg: Cw2981QueryMsg },
 }
 
 // Create the cw721 interface (only cw-multi-test)
-#[interface(cw721_metadata_onchain::InstantiateMsg, ExecuteMsg, QueryMsg, Empty)]
+#[interface(
+    cosmos_adventures_nft::msg::InstantiateMsg,
+    ExecuteMsg,
+    QueryMsg,
+    cosmos_adventures_nft::msg::MigrateMsg
+)]
 pub struct Cw721;
 
 impl<Chain: CwEnv> Uploadable for Cw721<Chain> {
     fn wrapper() -> <Mock as TxHandler>::ContractSource {
-        Box::new(ContractWrapper::new_with_empty(
-            cw721_metadata_onchain::entry::execute,
-            cw721_metadata_onchain::entry::instantiate,
-            cw721_metadata_onchain::entry::query,
-        ))
+        Box::new(
+            ContractWrapper::new_with_empty(
+                cosmos_adventures_nft::contract::execute,
+                cosmos_adventures_nft::contract::instantiate,
+                cosmos_adventures_nft::contract::query,
+            )
+            .with_migrate(cosmos_adventures_nft::contract::migrate),
+        )
     }
 }
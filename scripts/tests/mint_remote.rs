@@ -1,9 +1,12 @@
 use abstract_client::AbstractClient;
+use abstract_client::Account;
 use abstract_client::Application;
 
 use abstract_adapter::std::adapter::AdapterBaseMsg;
 use abstract_adapter::std::adapter::AdapterRequestMsg;
 use abstract_adapter::std::adapter::BaseExecuteMsg;
+use abstract_adapter::std::adapter::BaseInstantiateMsg;
+use abstract_adapter::std::app;
 use abstract_adapter::std::ibc_client;
 use abstract_adapter::std::ibc_host::HostAction;
 use abstract_adapter::std::manager;
@@ -20,27 +23,57 @@ use abstract_interface::ManagerExecFns;
 use abstract_interface::ProxyExecFns;
 use anyhow::Result as AnyResult;
 use ca_scripts::account::get_proxy_address;
+use ca_scripts::adapters::nft_metadata;
 use ca_scripts::adapters::setup_account;
 use ca_scripts::adapters::setup_adapters;
+use ca_scripts::adapters::setup_adapters_with_transfer_mode;
+use ca_scripts::hook_app::{HookAppInterface, HookAppQueryMsg, HOOK_APP_ID};
 use ca_scripts::ibc::ibc_abstract_setup;
 use ca_scripts::nft::Cw721;
+use ca_scripts::nft::ExecuteMsg as Cw721ExecuteMsg;
 use ca_scripts::nft::ExecuteMsgFns;
 use ca_scripts::nft::QueryMsgFns as _;
 use ca_scripts::MINT_COST;
 use ca_scripts::MINT_DENOM;
+use cosmos_adventures_hub::ibc::ics721::ICS721_VERSION;
+use cosmos_adventures_hub::msg::AccountInventoryResponse;
+use cosmos_adventures_hub::msg::ArrivalHook;
+use cosmos_adventures_hub::msg::CollectionToken;
 use cosmos_adventures_hub::msg::ExecuteMsg;
 use cosmos_adventures_hub::msg::HubExecuteMsg;
+use cosmos_adventures_hub::msg::HubHookMsg;
+use cosmos_adventures_hub::msg::HubQueryMsg;
+use cosmos_adventures_hub::msg::HubReceiveMsg;
+use cosmos_adventures_hub::msg::InstantiateMsg;
+use cosmos_adventures_hub::msg::QueryMsg;
+use cosmos_adventures_hub::msg::RateLimits;
+use cosmos_adventures_hub::msg::Recipient;
+use cosmos_adventures_hub::msg::Royalty;
+use cosmos_adventures_hub::msg::TransferMode;
+use cosmos_adventures_hub::msg::{MetadataField, MetadataPatch};
+use cosmos_adventures_hub::msg::{
+    TransferResponse, TransferSender, TransferStatus, TransfersResponse,
+};
 use cosmos_adventures_hub::{
     contract::HUB_ID,
     msg::{ConfigResponse, HubInstantiateMsg},
+    state::DEFAULT_COLLECTION,
     *,
 };
+use cosmos_adventures_nft::msg::{Cw2981QueryMsg, NftExecuteMsg, RoyaltiesInfoResponse};
 use cosmwasm_std::coin;
 use cosmwasm_std::coins;
 use cosmwasm_std::to_json_binary;
+use cosmwasm_std::Binary;
+use cosmwasm_std::Decimal;
+use cosmwasm_std::Empty;
+use cosmwasm_std::IbcOrder;
+use cosmwasm_std::Uint128;
+use cw721_base::MintMsg;
 use cw721_metadata_onchain::Metadata;
 // Use prelude to get all the necessary imports
 use cosmwasm_std::Addr;
+use cw_orch::mock::cw_multi_test::AppResponse;
 use cw_orch::{anyhow, prelude::*};
 use cw_orch_interchain::prelude::*;
 use minter::contract::interface::CosmosAdventuresMinter;
@@ -59,6 +92,269 @@ fn get_nft<Chain: CwEnv>(c: &CosmosAdventuresHub<Chain>) -> anyhow::Result<Cw721
     Ok(nft)
 }
 
+/// Installs the hub and the minter on the account and authorizes the minter to call the hub for it
+fn install_hub(
+    chain: &MockBech32,
+    account: &Account<MockBech32>,
+) -> anyhow::Result<(CosmosAdventuresHub<MockBech32>, Addr)> {
+    let hub: CosmosAdventuresHub<_> = account
+        .install_adapter::<CosmosAdventuresHub<_>>(&[])?
+        .module()?;
+    let minter = account.install_adapter::<CosmosAdventuresMinter<_>>(&[])?;
+
+    let abstract_account =
+        AbstractAccount::new(&Abstract::load_from(chain.clone())?, account.id()?);
+    abstract_account.manager.execute_on_module(
+        HUB_ID,
+        ExecuteMsg::Base(BaseExecuteMsg {
+            proxy_address: None,
+            msg: AdapterBaseMsg::UpdateAuthorizedAddresses {
+                to_add: vec![minter.address()?.to_string()],
+                to_remove: vec![],
+            },
+        }),
+    )?;
+
+    Ok((hub, minter.address()?))
+}
+
+/// Sends a request to the hub as the module, on behalf of the account
+fn module_request(
+    hub: &CosmosAdventuresHub<MockBech32>,
+    module: &Addr,
+    account: &Account<MockBech32>,
+    request: HubExecuteMsg,
+) -> anyhow::Result<()> {
+    hub.call_as(module).execute(
+        &ExecuteMsg::Module(AdapterRequestMsg {
+            proxy_address: Some(account.proxy()?.to_string()),
+            request,
+        }),
+        None,
+    )?;
+    Ok(())
+}
+
+/// Sends a request to the hub as the manager of the account
+fn account_request(
+    hub: &CosmosAdventuresHub<MockBech32>,
+    account: &Account<MockBech32>,
+    request: HubExecuteMsg,
+) -> anyhow::Result<AppResponse> {
+    Ok(hub.call_as(&account.manager()?).execute(
+        &ExecuteMsg::Module(AdapterRequestMsg {
+            proxy_address: Some(account.proxy()?.to_string()),
+            request,
+        }),
+        None,
+    )?)
+}
+
+/// Sends a request to the hub on behalf of its admin account
+fn admin_request(
+    client: &AbstractClient<MockBech32>,
+    hub: &CosmosAdventuresHub<MockBech32>,
+    request: HubExecuteMsg,
+) -> anyhow::Result<()> {
+    let admin = client.account_from(hub.config()?.admin_account)?;
+    account_request(hub, &admin, request)?;
+    Ok(())
+}
+
+/// NFT contract of a collection of the hub
+fn collection_nft(
+    hub: &CosmosAdventuresHub<MockBech32>,
+    collection_id: &str,
+) -> anyhow::Result<Cw721<MockBech32>> {
+    let collection = hub
+        .collections(None, None)?
+        .collections
+        .into_iter()
+        .find(|collection| collection.collection_id == collection_id)
+        .expect("the collection exists");
+
+    let nft = Cw721::new(collection_id, hub.get_chain().clone());
+    nft.set_address(&Addr::unchecked(collection.nft));
+    Ok(nft)
+}
+
+/// Mints a token of the default collection to the account through the minter and returns its id
+fn mint_token(
+    hub: &CosmosAdventuresHub<MockBech32>,
+    minter: &Addr,
+    account: &Account<MockBech32>,
+    royalty: Option<Royalty>,
+) -> anyhow::Result<String> {
+    let token_id = hub.next_token_id()?.next_token_id;
+    let (token_uri, metadata) = nft_metadata();
+    module_request(
+        hub,
+        minter,
+        account,
+        HubExecuteMsg::Mint {
+            module_id: MINTER_ID.to_string(),
+            collection_id: None,
+            token_uri,
+            metadata,
+            royalty,
+        },
+    )?;
+    Ok(token_id)
+}
+
+/// Instantiates a hub outside of the Abstract registry, at the instantiate2 address of `salt`
+fn instantiate_standalone_hub(
+    chain: &MockBech32,
+    salt: &str,
+    msg: HubInstantiateMsg,
+) -> anyhow::Result<CosmosAdventuresHub<MockBech32>> {
+    let hub = CosmosAdventuresHub::new(salt, chain.clone());
+    hub.set_code_id(CosmosAdventuresHub::new(HUB_ID, chain.clone()).code_id()?);
+
+    let abstr = Abstract::load_from(chain.clone())?;
+    hub.instantiate2(
+        &InstantiateMsg {
+            base: BaseInstantiateMsg {
+                ans_host_address: abstr.ans_host.address()?.to_string(),
+                version_control_address: abstr.version_control.address()?.to_string(),
+            },
+            module: msg,
+        },
+        None,
+        None,
+        Binary::from(salt.as_bytes()),
+    )?;
+    Ok(hub)
+}
+
+/// Address of the hub instantiated by `instantiate_standalone_hub` with `salt`
+fn standalone_hub_address(chain: &MockBech32, salt: &str) -> anyhow::Result<Addr> {
+    let code_id = CosmosAdventuresHub::new(HUB_ID, chain.clone()).code_id()?;
+    let address = chain.wasm_querier().instantiate2_addr(
+        code_id,
+        chain.sender().to_string(),
+        Binary::from(salt.as_bytes()),
+    )?;
+    Ok(Addr::unchecked(address))
+}
+
+/// Transfer of a token of the default collection to the remote account on phoenix
+fn ibc_transfer(token_id: &str) -> HubExecuteMsg {
+    HubExecuteMsg::IbcTransfer {
+        token_id: token_id.to_string(),
+        collection_id: None,
+        recipient_chain: "phoenix".to_string(),
+        recipient: None,
+        fallback_address: None,
+        route: None,
+        on_arrival: None,
+    }
+}
+
+/// Cross-chain transfers of a token, as recorded by the hub
+fn token_transfers(
+    hub: &CosmosAdventuresHub<MockBech32>,
+    token_id: &str,
+) -> anyhow::Result<Vec<TransferResponse>> {
+    let response: TransfersResponse =
+        hub.query(&QueryMsg::Module(HubQueryMsg::TransfersByToken {
+            token_id: token_id.to_string(),
+            collection_id: None,
+            status: None,
+            start_after: None,
+            limit: None,
+        }))?;
+    Ok(response.transfers)
+}
+
+/// Juno and phoenix, connected both ways
+/// The account on juno has the hub and the minter installed and its remote account exists on phoenix
+struct TransferEnv {
+    interchain: MockBech32InterchainEnv,
+    juno: MockBech32,
+    terra: MockBech32,
+    src_client: AbstractClient<MockBech32>,
+    dst_client: AbstractClient<MockBech32>,
+    src_account: Account<MockBech32>,
+    src_hub: CosmosAdventuresHub<MockBech32>,
+    minter: Addr,
+    dst_hub: CosmosAdventuresHub<MockBech32>,
+}
+
+impl TransferEnv {
+    fn setup() -> anyhow::Result<Self> {
+        Self::setup_with_transfer_mode(None)
+    }
+
+    /// `transfer_mode` is the transfer mode of the juno hub
+    fn setup_with_transfer_mode(transfer_mode: Option<TransferMode>) -> anyhow::Result<Self> {
+        let _ = env_logger::try_init();
+        let interchain =
+            MockBech32InterchainEnv::new(vec![("juno-1", "juno"), ("phoenix-1", "terra")]);
+        let juno = interchain.chain("juno-1")?;
+        let terra = interchain.chain("phoenix-1")?;
+
+        let src_client = setup_adapters_with_transfer_mode(juno.clone(), transfer_mode)?;
+        let src_account = setup_account(&src_client)?;
+        let dst_client = setup_adapters(terra.clone())?;
+        ibc_abstract_setup(&interchain, "juno-1", "phoenix-1")?;
+        ibc_abstract_setup(&interchain, "phoenix-1", "juno-1")?;
+
+        let (src_hub, minter) = install_hub(&juno, &src_account)?;
+        let dst_hub = CosmosAdventuresHub::new(HUB_ID, terra.clone());
+
+        // The remote account is created by its first remote action
+        let abstract_account =
+            AbstractAccount::new(&Abstract::load_from(juno.clone())?, src_account.id()?);
+        let remote_actions_response = abstract_account.manager.execute_on_module(
+            PROXY,
+            proxy::ExecuteMsg::IbcAction {
+                msg: ibc_client::ExecuteMsg::RemoteAction {
+                    host_chain: "phoenix".to_string(),
+                    action: HostAction::Dispatch {
+                        manager_msgs: vec![manager::ExecuteMsg::UpdateSettings {
+                            ibc_enabled: Some(true),
+                        }],
+                    },
+                },
+            },
+        )?;
+        interchain.check_ibc("juno-1", remote_actions_response)?;
+
+        Ok(Self {
+            interchain,
+            juno,
+            terra,
+            src_client,
+            dst_client,
+            src_account,
+            src_hub,
+            minter,
+            dst_hub,
+        })
+    }
+
+    /// Remote account of the juno account on phoenix
+    fn remote_account(&self) -> anyhow::Result<Account<MockBech32>> {
+        Ok(self.dst_client.account_from(AccountId::remote(
+            self.src_account.id()?.seq(),
+            vec![ChainName::from_chain_id("juno-1")],
+        )?)?)
+    }
+
+    fn mint(&self) -> anyhow::Result<String> {
+        mint_token(&self.src_hub, &self.minter, &self.src_account, None)
+    }
+
+    /// Sends a request of the juno account to its hub and relays the IBC packets it creates
+    /// Failed packets are relayed as well, their errors are acknowledged back to juno
+    fn relayed_request(&self, request: HubExecuteMsg) -> anyhow::Result<()> {
+        let response = account_request(&self.src_hub, &self.src_account, request)?;
+        self.interchain.wait_ibc("juno-1", response)?;
+        Ok(())
+    }
+}
+
 #[test]
 fn successful_install() -> anyhow::Result<()> {
     let chain = MockBech32::new("mock");
@@ -288,6 +584,1242 @@ fn successful_mint_send_back() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn royalties_are_answered_by_the_nft_contract() -> anyhow::Result<()> {
+    let chain = MockBech32::new("mock");
+    let client = setup_adapters(chain.clone())?;
+    let account = setup_account(&client)?;
+    let (hub, minter) = install_hub(&chain, &account)?;
+
+    // A 2.5% royalty is paid to the proxy of the recipient account
+    let royalty = Royalty {
+        recipient: account.id()?,
+        share: Decimal::permille(25),
+    };
+    let token_id = mint_token(&hub, &minter, &account, Some(royalty))?;
+
+    let nft = get_nft(&hub)?;
+    let royalty_info: RoyaltiesInfoResponse = nft.extension(Cw2981QueryMsg::RoyaltyInfo {
+        token_id,
+        sale_price: Uint128::new(1000),
+    })?;
+    assert_eq!(
+        royalty_info,
+        RoyaltiesInfoResponse {
+            address: account.proxy()?.to_string(),
+            royalty_amount: Uint128::new(25),
+        }
+    );
+
+    // Shares above 1 are rejected
+    let royalty = Royalty {
+        recipient: account.id()?,
+        share: Decimal::percent(101),
+    };
+    assert!(mint_token(&hub, &minter, &account, Some(royalty)).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn soulbound_tokens_can_only_be_burned() -> anyhow::Result<()> {
+    let chain = MockBech32::new("mock");
+    let client = setup_adapters(chain.clone())?;
+    let account = setup_account(&client)?;
+    let (hub, minter) = install_hub(&chain, &account)?;
+
+    admin_request(
+        &client,
+        &hub,
+        HubExecuteMsg::CreateCollection {
+            collection_id: "badges".to_string(),
+            name: "Badges".to_string(),
+            symbol: "BDG".to_string(),
+            soulbound: true,
+        },
+    )?;
+    let token_id = hub.next_token_id()?.next_token_id;
+    let (token_uri, metadata) = nft_metadata();
+    module_request(
+        &hub,
+        &minter,
+        &account,
+        HubExecuteMsg::Mint {
+            module_id: MINTER_ID.to_string(),
+            collection_id: Some("badges".to_string()),
+            token_uri,
+            metadata,
+            royalty: None,
+        },
+    )?;
+
+    // The token can't leave the account, neither with cw721 transfers nor cross-chain
+    let badges = collection_nft(&hub, "badges")?;
+    assert!(badges
+        .call_as(&account.proxy()?)
+        .transfer_nft(chain.sender().to_string(), token_id.clone())
+        .is_err());
+    assert!(module_request(
+        &hub,
+        &minter,
+        &account,
+        HubExecuteMsg::IbcTransfer {
+            token_id: token_id.clone(),
+            collection_id: Some("badges".to_string()),
+            recipient_chain: "phoenix".to_string(),
+            recipient: None,
+            fallback_address: None,
+            route: None,
+            on_arrival: None,
+        },
+    )
+    .is_err());
+
+    // The hub burns it for the module, without any approval of the account
+    module_request(
+        &hub,
+        &minter,
+        &account,
+        HubExecuteMsg::Burn {
+            module_id: MINTER_ID.to_string(),
+            token_id,
+            collection_id: Some("badges".to_string()),
+        },
+    )?;
+    assert_eq!(badges.num_tokens()?.count, 0);
+
+    Ok(())
+}
+
+#[test]
+fn failed_transfers_give_the_tokens_back() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_id = env.mint()?;
+
+    // The destination hub refuses the tokens
+    admin_request(
+        &env.dst_client,
+        &env.dst_hub,
+        HubExecuteMsg::SetPaused { paused: true },
+    )?;
+    env.relayed_request(ibc_transfer(&token_id))?;
+
+    // The escrowed token is back in the account and was not minted on phoenix
+    let nft = get_nft(&env.src_hub)?;
+    assert_eq!(
+        env.src_account.proxy()?,
+        nft.owner_of(token_id.clone(), None)?.owner
+    );
+    assert_eq!(get_nft(&env.dst_hub)?.num_tokens()?.count, 0);
+
+    let transfers = token_transfers(&env.src_hub, &token_id)?;
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].status, TransferStatus::Failed);
+    assert!(transfers[0].error.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn colliding_mints_give_the_tokens_back() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_id = env.mint()?;
+
+    // A token with the same id already exists on phoenix
+    let dst_nft = get_nft(&env.dst_hub)?;
+    let squatter = env.terra.addr_make("squatter");
+    dst_nft.call_as(&env.dst_hub.address()?).execute(
+        &Cw721ExecuteMsg::Mint(MintMsg {
+            token_id: token_id.clone(),
+            owner: squatter.to_string(),
+            token_uri: None,
+            extension: None,
+        }),
+        None,
+    )?;
+    env.relayed_request(ibc_transfer(&token_id))?;
+
+    // The IBC mint fails on phoenix and the token is given back on juno
+    let src_nft = get_nft(&env.src_hub)?;
+    assert_eq!(
+        env.src_account.proxy()?,
+        src_nft.owner_of(token_id.clone(), None)?.owner
+    );
+    assert_eq!(
+        squatter.to_string(),
+        dst_nft.owner_of(token_id.clone(), None)?.owner
+    );
+    assert_eq!(dst_nft.num_tokens()?.count, 1);
+
+    let transfers = token_transfers(&env.src_hub, &token_id)?;
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers[0].status, TransferStatus::Failed);
+    assert!(transfers[0]
+        .error
+        .as_ref()
+        .is_some_and(|error| error.contains(&token_id)));
+
+    Ok(())
+}
+
+#[test]
+fn transfers_are_recorded_until_completed() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_id = env.mint()?;
+    env.relayed_request(ibc_transfer(&token_id))?;
+
+    // The token now belongs to the remote account
+    assert_eq!(
+        env.remote_account()?.proxy()?,
+        get_nft(&env.dst_hub)?
+            .owner_of(token_id.clone(), None)?
+            .owner
+    );
+
+    // The transfer of the account is completed
+    let completed: TransfersResponse =
+        env.src_hub
+            .query(&QueryMsg::Module(HubQueryMsg::TransfersBySender {
+                sender: TransferSender::Account(env.src_account.id()?),
+                status: Some(TransferStatus::Completed),
+                start_after: None,
+                limit: None,
+            }))?;
+    assert_eq!(completed.transfers.len(), 1);
+    let transfer = &completed.transfers[0];
+    assert_eq!(transfer.token_id, token_id);
+    assert_eq!(transfer.destination_chain, "phoenix");
+    assert_eq!(transfer.error, None);
+
+    // Nothing is left in transit
+    let pending: TransfersResponse =
+        env.src_hub
+            .query(&QueryMsg::Module(HubQueryMsg::TransfersBySender {
+                sender: TransferSender::Account(env.src_account.id()?),
+                status: Some(TransferStatus::Pending),
+                start_after: None,
+                limit: None,
+            }))?;
+    assert!(pending.transfers.is_empty());
+    assert_eq!(
+        token_transfers(&env.src_hub, &token_id)?,
+        completed.transfers
+    );
+
+    Ok(())
+}
+
+#[test]
+fn batches_are_transferred_in_one_packet() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_ids = vec![env.mint()?, env.mint()?];
+
+    env.relayed_request(HubExecuteMsg::IbcTransferBatch {
+        token_ids: token_ids.clone(),
+        collection_id: None,
+        recipient_chain: "phoenix".to_string(),
+        recipient: None,
+        fallback_address: None,
+        route: None,
+        on_arrival: None,
+    })?;
+
+    // Both tokens left juno and belong to the remote account
+    assert_eq!(get_nft(&env.src_hub)?.num_tokens()?.count, 0);
+    let remote_proxy = env.remote_account()?.proxy()?;
+    let dst_nft = get_nft(&env.dst_hub)?;
+    for token_id in &token_ids {
+        assert_eq!(
+            remote_proxy,
+            dst_nft.owner_of(token_id.clone(), None)?.owner
+        );
+        let transfers = token_transfers(&env.src_hub, token_id)?;
+        assert_eq!(transfers[0].status, TransferStatus::Completed);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn tokens_can_be_sent_to_another_account() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_id = env.mint()?;
+
+    // The recipient is a local account of phoenix
+    let recipient = setup_account(&env.dst_client)?;
+    env.relayed_request(HubExecuteMsg::IbcTransfer {
+        token_id: token_id.clone(),
+        collection_id: None,
+        recipient_chain: "phoenix".to_string(),
+        recipient: Some(Recipient::Account(recipient.id()?)),
+        fallback_address: None,
+        route: None,
+        on_arrival: None,
+    })?;
+
+    assert_eq!(
+        recipient.proxy()?,
+        get_nft(&env.dst_hub)?.owner_of(token_id, None)?.owner
+    );
+
+    Ok(())
+}
+
+#[test]
+fn tokens_are_routed_through_intermediate_chains() -> anyhow::Result<()> {
+    let _ = env_logger::try_init();
+    let interchain = MockBech32InterchainEnv::new(vec![
+        ("juno-1", "juno"),
+        ("osmosis-1", "osmo"),
+        ("phoenix-1", "terra"),
+    ]);
+    let juno = interchain.chain("juno-1")?;
+    let osmosis = interchain.chain("osmosis-1")?;
+    let terra = interchain.chain("phoenix-1")?;
+
+    let src_client = setup_adapters(juno.clone())?;
+    let src_account = setup_account(&src_client)?;
+    setup_adapters(osmosis.clone())?;
+    setup_adapters(terra.clone())?;
+    // Osmosis forwards the tokens to phoenix and sends the result of the route back to juno
+    ibc_abstract_setup(&interchain, "juno-1", "osmosis-1")?;
+    ibc_abstract_setup(&interchain, "osmosis-1", "juno-1")?;
+    ibc_abstract_setup(&interchain, "osmosis-1", "phoenix-1")?;
+
+    let (src_hub, minter) = install_hub(&juno, &src_account)?;
+    let token_id = mint_token(&src_hub, &minter, &src_account, None)?;
+
+    let wallet = terra.addr_make("wallet");
+    let response = account_request(
+        &src_hub,
+        &src_account,
+        HubExecuteMsg::IbcTransfer {
+            token_id: token_id.clone(),
+            collection_id: None,
+            recipient_chain: "phoenix".to_string(),
+            recipient: Some(Recipient::Address(wallet.to_string())),
+            fallback_address: None,
+            route: Some(vec!["osmosis".to_string()]),
+            on_arrival: None,
+        },
+    )?;
+    interchain.wait_ibc("juno-1", response)?;
+
+    // The token reached phoenix, osmosis doesn't hold it anymore
+    let dst_hub = CosmosAdventuresHub::new(HUB_ID, terra.clone());
+    assert_eq!(
+        wallet,
+        get_nft(&dst_hub)?.owner_of(token_id.clone(), None)?.owner
+    );
+    let hop_hub = CosmosAdventuresHub::new(HUB_ID, osmosis.clone());
+    assert_eq!(get_nft(&hop_hub)?.num_tokens()?.count, 0);
+
+    // Juno learned the result of the whole route
+    let transfers = token_transfers(&src_hub, &token_id)?;
+    assert_eq!(transfers[0].route, vec!["osmosis", "phoenix"]);
+    assert_eq!(transfers[0].status, TransferStatus::Completed);
+
+    Ok(())
+}
+
+#[test]
+fn escrowed_tokens_are_given_back_when_they_return() -> anyhow::Result<()> {
+    let env = TransferEnv::setup_with_transfer_mode(Some(TransferMode::Escrow))?;
+    let token_id = env.mint()?;
+
+    // The token leaves for an account of phoenix that has the hub installed
+    let holder = setup_account(&env.dst_client)?;
+    holder.install_adapter::<CosmosAdventuresHub<_>>(&[])?;
+    env.relayed_request(HubExecuteMsg::IbcTransfer {
+        token_id: token_id.clone(),
+        collection_id: None,
+        recipient_chain: "phoenix".to_string(),
+        recipient: Some(Recipient::Account(holder.id()?)),
+        fallback_address: None,
+        route: None,
+        on_arrival: None,
+    })?;
+
+    // Juno keeps it escrowed in the hub instead of burning it
+    let src_nft = get_nft(&env.src_hub)?;
+    assert_eq!(
+        env.src_hub.address()?,
+        src_nft.owner_of(token_id.clone(), None)?.owner
+    );
+    assert_eq!(env.src_hub.supply()?.escrowed, 1);
+
+    // Its metadata changes while it is on phoenix
+    let dst_nft = get_nft(&env.dst_hub)?;
+    let mut metadata = dst_nft.nft_info(token_id.clone())?.extension.unwrap();
+    metadata.name = Some("Renamed".to_string());
+    dst_nft.call_as(&env.dst_hub.address()?).execute(
+        &Cw721ExecuteMsg::Extension {
+            msg: NftExecuteMsg::UpdateMetadata {
+                token_id: token_id.clone(),
+                token_uri: None,
+                extension: Some(metadata.clone()),
+            },
+        },
+        None,
+    )?;
+
+    // Once the token comes back, the escrowed copy is given to the recipient with the new metadata
+    let response = account_request(
+        &env.dst_hub,
+        &holder,
+        HubExecuteMsg::IbcTransfer {
+            token_id: token_id.clone(),
+            collection_id: None,
+            recipient_chain: "juno".to_string(),
+            recipient: Some(Recipient::Account(env.src_account.id()?)),
+            fallback_address: None,
+            route: None,
+            on_arrival: None,
+        },
+    )?;
+    env.interchain.wait_ibc("phoenix-1", response)?;
+
+    assert_eq!(
+        env.src_account.proxy()?,
+        src_nft.owner_of(token_id.clone(), None)?.owner
+    );
+    assert_eq!(src_nft.nft_info(token_id)?.extension, Some(metadata));
+    assert_eq!(get_nft(&env.dst_hub)?.num_tokens()?.count, 0);
+    let supply = env.src_hub.supply()?;
+    assert_eq!(supply.escrowed, 0);
+    assert_eq!(supply.received, 0);
+
+    Ok(())
+}
+
+#[test]
+fn rejected_ics721_packets_give_the_tokens_back() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_id = env.mint()?;
+
+    // Tokens can only leave through ICS-721 channels opened with the hub
+    let ics721_transfer = |channel_id: &str| HubExecuteMsg::Ics721Transfer {
+        token_ids: vec![token_id.clone()],
+        collection_id: None,
+        channel_id: channel_id.to_string(),
+        receiver: env.dst_hub.address().unwrap().to_string(),
+        memo: None,
+    };
+    assert!(account_request(
+        &env.src_hub,
+        &env.src_account,
+        ics721_transfer("channel-42")
+    )
+    .is_err());
+
+    // The phoenix hub only accepts its own tokens back, it rejects the juno class
+    let channel = env.interchain.create_contract_channel(
+        &env.src_hub,
+        &env.dst_hub,
+        ICS721_VERSION,
+        Some(IbcOrder::Unordered),
+    )?;
+    let (src_port, _) = channel
+        .interchain_channel
+        .get_ordered_ports_from("juno-1")?;
+    let channel_id = src_port.channel.expect("the channel is open").to_string();
+    let response = account_request(&env.src_hub, &env.src_account, ics721_transfer(&channel_id))?;
+    env.interchain.wait_ibc("juno-1", response)?;
+
+    // The escrowed token is back in the account
+    assert_eq!(
+        env.src_account.proxy()?,
+        get_nft(&env.src_hub)?
+            .owner_of(token_id.clone(), None)?
+            .owner
+    );
+    assert_eq!(env.src_hub.supply()?.escrowed, 0);
+    let inventory = env
+        .src_hub
+        .account_inventory(env.src_account.id()?, None, None)?;
+    assert_eq!(inventory.tokens[0].token_id, token_id);
+
+    Ok(())
+}
+
+#[test]
+fn token_ids_are_looked_up_before_minting() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_id = env.mint()?;
+    env.relayed_request(ibc_transfer(&token_id))?;
+
+    // The id is now used on phoenix, an incoming copy of it would be refused
+    let dst_token = env.dst_hub.token_exists(token_id.clone(), None)?;
+    assert!(dst_token.exists);
+    assert_eq!(
+        dst_token.owner,
+        Some(env.remote_account()?.proxy()?.to_string())
+    );
+    assert!(!env.src_hub.token_exists(token_id, None)?.exists);
+
+    Ok(())
+}
+
+#[test]
+fn transfer_fees_are_paid_to_the_admin_account() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let fee = coin(100, "ujuno");
+    admin_request(
+        &env.src_client,
+        &env.src_hub,
+        HubExecuteMsg::SetTransferFee {
+            chain: "phoenix".to_string(),
+            fee: Some(fee.clone()),
+        },
+    )?;
+    assert_eq!(
+        env.src_hub.transfer_fee("phoenix".to_string())?.fee,
+        Some(fee.clone())
+    );
+
+    // The account can't leave without paying
+    let token_id = env.mint()?;
+    assert!(account_request(&env.src_hub, &env.src_account, ibc_transfer(&token_id)).is_err());
+
+    env.juno
+        .add_balance(&env.src_account.proxy()?, vec![fee.clone()])?;
+    env.relayed_request(ibc_transfer(&token_id))?;
+
+    let admin = env
+        .src_client
+        .account_from(env.src_hub.config()?.admin_account)?;
+    assert_eq!(
+        env.juno.query_balance(&admin.proxy()?, &fee.denom)?,
+        fee.amount
+    );
+    assert_eq!(
+        env.juno
+            .query_balance(&env.src_account.proxy()?, &fee.denom)?,
+        Uint128::zero()
+    );
+    assert!(get_nft(&env.dst_hub)?.owner_of(token_id, None).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn closed_chains_are_refused() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_id = env.mint()?;
+    let update_settings = |chain: &str, allowlisted: Option<bool>, paused: Option<bool>| {
+        admin_request(
+            &env.src_client,
+            &env.src_hub,
+            HubExecuteMsg::UpdateChainSettings {
+                chain: chain.to_string(),
+                allowlisted,
+                denylisted: None,
+                paused,
+            },
+        )
+    };
+
+    update_settings("phoenix", None, Some(true))?;
+    assert!(!env.src_hub.chain_settings("phoenix".to_string())?.open);
+    assert!(account_request(&env.src_hub, &env.src_account, ibc_transfer(&token_id)).is_err());
+
+    // Once a chain is allowlisted, the other chains are closed
+    update_settings("phoenix", None, Some(false))?;
+    update_settings("osmosis", Some(true), None)?;
+    assert!(!env.src_hub.chain_settings("phoenix".to_string())?.open);
+    assert!(account_request(&env.src_hub, &env.src_account, ibc_transfer(&token_id)).is_err());
+
+    update_settings("phoenix", Some(true), None)?;
+    assert!(env.src_hub.chain_settings("phoenix".to_string())?.open);
+    env.relayed_request(ibc_transfer(&token_id))?;
+    assert!(get_nft(&env.dst_hub)?.owner_of(token_id, None).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn accounts_are_rate_limited() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    assert!(admin_request(
+        &env.src_client,
+        &env.src_hub,
+        HubExecuteMsg::SetRateLimits {
+            rate_limits: Some(RateLimits {
+                window: 0,
+                max_tokens_per_account: Some(1),
+                max_tokens_per_chain: None,
+            }),
+        },
+    )
+    .is_err());
+    admin_request(
+        &env.src_client,
+        &env.src_hub,
+        HubExecuteMsg::SetRateLimits {
+            rate_limits: Some(RateLimits {
+                window: 3600,
+                max_tokens_per_account: Some(1),
+                max_tokens_per_chain: None,
+            }),
+        },
+    )?;
+    let sender = TransferSender::Account(env.src_account.id()?);
+    assert_eq!(
+        env.src_hub
+            .remaining_quota(sender.clone(), None)?
+            .account_remaining,
+        Some(1)
+    );
+
+    let first_token = env.mint()?;
+    let second_token = env.mint()?;
+    env.relayed_request(ibc_transfer(&first_token))?;
+    assert_eq!(
+        env.src_hub
+            .remaining_quota(sender.clone(), None)?
+            .account_remaining,
+        Some(0)
+    );
+    assert!(account_request(&env.src_hub, &env.src_account, ibc_transfer(&second_token)).is_err());
+
+    // The quota is renewed in the next window
+    env.juno.wait_seconds(3600)?;
+    env.relayed_request(ibc_transfer(&second_token))?;
+    assert_eq!(get_nft(&env.dst_hub)?.num_tokens()?.count, 2);
+
+    Ok(())
+}
+
+#[test]
+fn wallets_send_tokens_with_send_nft() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_id = env.mint()?;
+
+    // The token is given to a plain wallet of phoenix
+    let wallet = env.terra.addr_make("wallet");
+    env.relayed_request(HubExecuteMsg::IbcTransfer {
+        token_id: token_id.clone(),
+        collection_id: None,
+        recipient_chain: "phoenix".to_string(),
+        recipient: Some(Recipient::Address(wallet.to_string())),
+        fallback_address: None,
+        route: None,
+        on_arrival: None,
+    })?;
+
+    // The wallet sends it back to the juno account through the phoenix hub
+    let response = get_nft(&env.dst_hub)?.call_as(&wallet).execute(
+        &Cw721ExecuteMsg::SendNft {
+            contract: env.dst_hub.address()?.to_string(),
+            token_id: token_id.clone(),
+            msg: to_json_binary(&HubReceiveMsg::IbcTransfer {
+                recipient_chain: "juno".to_string(),
+                recipient: Recipient::Account(env.src_account.id()?),
+                fallback_address: None,
+                route: None,
+                on_arrival: None,
+            })?,
+        },
+        None,
+    )?;
+    env.interchain.wait_ibc("phoenix-1", response)?;
+
+    assert_eq!(
+        env.src_account.proxy()?,
+        get_nft(&env.src_hub)?.owner_of(token_id, None)?.owner
+    );
+    let wallet_transfers: TransfersResponse =
+        env.dst_hub
+            .query(&QueryMsg::Module(HubQueryMsg::TransfersBySender {
+                sender: TransferSender::Address(wallet.to_string()),
+                status: Some(TransferStatus::Completed),
+                start_after: None,
+                limit: None,
+            }))?;
+    assert_eq!(wallet_transfers.transfers.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn tokens_wait_for_accounts_that_dont_exist_yet() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let first_token = env.mint()?;
+    let second_token = env.mint()?;
+
+    // This other juno account has no remote account on phoenix yet
+    let late_account = setup_account(&env.src_client)?;
+    let late_remote_id = AccountId::remote(
+        late_account.id()?.seq(),
+        vec![ChainName::from_chain_id("juno-1")],
+    )?;
+    let transfer_to_late_account =
+        |token_id: &str, fallback_address: Option<String>| HubExecuteMsg::IbcTransfer {
+            token_id: token_id.to_string(),
+            collection_id: None,
+            recipient_chain: "phoenix".to_string(),
+            recipient: Some(Recipient::Account(late_remote_id.clone())),
+            fallback_address,
+            route: None,
+            on_arrival: None,
+        };
+    let dst_nft = get_nft(&env.dst_hub)?;
+
+    // The token goes to the fallback address when there is one
+    let fallback = env.terra.addr_make("fallback");
+    env.relayed_request(transfer_to_late_account(
+        &first_token,
+        Some(fallback.to_string()),
+    ))?;
+    assert_eq!(fallback, dst_nft.owner_of(first_token, None)?.owner);
+
+    // Otherwise the hub holds it until the account claims it
+    env.relayed_request(transfer_to_late_account(&second_token, None))?;
+    assert_eq!(
+        env.dst_hub.address()?,
+        dst_nft.owner_of(second_token.clone(), None)?.owner
+    );
+    assert_eq!(
+        env.dst_hub
+            .claimable_tokens(late_remote_id.clone(), None, None)?
+            .tokens,
+        vec![CollectionToken {
+            collection_id: DEFAULT_COLLECTION.to_string(),
+            token_id: second_token.clone(),
+        }]
+    );
+
+    // The remote account is created with the hub installed and claims the token
+    let abstract_account =
+        AbstractAccount::new(&Abstract::load_from(env.juno.clone())?, late_account.id()?);
+    let remote_actions_response = abstract_account.manager.execute_on_module(
+        PROXY,
+        proxy::ExecuteMsg::IbcAction {
+            msg: ibc_client::ExecuteMsg::RemoteAction {
+                host_chain: "phoenix".to_string(),
+                action: HostAction::Dispatch {
+                    manager_msgs: vec![
+                        manager::ExecuteMsg::InstallModules {
+                            modules: vec![ModuleInstallConfig::new(
+                                CosmosAdventuresHub::<Mock>::module_info()?,
+                                None,
+                            )],
+                        },
+                        manager::ExecuteMsg::ExecOnModule {
+                            module_id: HUB_ID.to_string(),
+                            exec_msg: to_json_binary(&ExecuteMsg::Module(AdapterRequestMsg {
+                                proxy_address: None,
+                                request: HubExecuteMsg::ClaimPending {},
+                            }))?,
+                        },
+                    ],
+                },
+            },
+        },
+    )?;
+    env.interchain
+        .check_ibc("juno-1", remote_actions_response)?;
+
+    let late_remote = env.dst_client.account_from(late_remote_id.clone())?;
+    assert_eq!(
+        late_remote.proxy()?,
+        dst_nft.owner_of(second_token, None)?.owner
+    );
+    assert!(env
+        .dst_hub
+        .claimable_tokens(late_remote_id, None, None)?
+        .tokens
+        .is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn failed_arrival_hooks_are_reported() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_id = env.mint()?;
+
+    // The minter is installed on the remote account but is an adapter, which can't be called on arrival
+    let abstract_account = AbstractAccount::new(
+        &Abstract::load_from(env.juno.clone())?,
+        env.src_account.id()?,
+    );
+    let remote_actions_response = abstract_account.manager.execute_on_module(
+        PROXY,
+        proxy::ExecuteMsg::IbcAction {
+            msg: ibc_client::ExecuteMsg::RemoteAction {
+                host_chain: "phoenix".to_string(),
+                action: HostAction::Dispatch {
+                    manager_msgs: vec![manager::ExecuteMsg::InstallModules {
+                        modules: vec![ModuleInstallConfig::new(
+                            CosmosAdventuresMinter::<Mock>::module_info()?,
+                            None,
+                        )],
+                    }],
+                },
+            },
+        },
+    )?;
+    env.interchain
+        .check_ibc("juno-1", remote_actions_response)?;
+
+    env.relayed_request(HubExecuteMsg::IbcTransfer {
+        token_id: token_id.clone(),
+        collection_id: None,
+        recipient_chain: "phoenix".to_string(),
+        recipient: None,
+        fallback_address: None,
+        route: None,
+        on_arrival: Some(ArrivalHook {
+            module_id: MINTER_ID.to_string(),
+            msg: to_json_binary(&"arrived")?,
+        }),
+    })?;
+
+    // The token is still received and the hook error is sent back with the acknowledgement
+    assert_eq!(
+        env.remote_account()?.proxy()?,
+        get_nft(&env.dst_hub)?
+            .owner_of(token_id.clone(), None)?
+            .owner
+    );
+    let transfers = token_transfers(&env.src_hub, &token_id)?;
+    assert_eq!(transfers[0].status, TransferStatus::Completed);
+    assert!(transfers[0].arrival_hook_error.is_some());
+
+    Ok(())
+}
+
+#[test]
+fn arrival_hooks_call_the_recipient_app() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let token_id = env.mint()?;
+
+    // The hook app is published in the hub namespace and installed on the remote account
+    env.dst_client
+        .publisher_builder(Namespace::from_id(HOOK_APP_ID)?)
+        .install_on_sub_account(false)
+        .build()?
+        .publish_app::<HookAppInterface<_>>()?;
+    let abstract_account = AbstractAccount::new(
+        &Abstract::load_from(env.juno.clone())?,
+        env.src_account.id()?,
+    );
+    let remote_actions_response = abstract_account.manager.execute_on_module(
+        PROXY,
+        proxy::ExecuteMsg::IbcAction {
+            msg: ibc_client::ExecuteMsg::RemoteAction {
+                host_chain: "phoenix".to_string(),
+                action: HostAction::Dispatch {
+                    manager_msgs: vec![manager::ExecuteMsg::InstallModules {
+                        modules: vec![ModuleInstallConfig::new(
+                            HookAppInterface::<Mock>::module_info()?,
+                            Some(to_json_binary(&Empty {})?),
+                        )],
+                    }],
+                },
+            },
+        },
+    )?;
+    env.interchain
+        .check_ibc("juno-1", remote_actions_response)?;
+
+    env.relayed_request(HubExecuteMsg::IbcTransfer {
+        token_id: token_id.clone(),
+        collection_id: None,
+        recipient_chain: "phoenix".to_string(),
+        recipient: None,
+        fallback_address: None,
+        route: None,
+        on_arrival: Some(ArrivalHook {
+            module_id: HOOK_APP_ID.to_string(),
+            msg: to_json_binary(&"arrived")?,
+        }),
+    })?;
+
+    let hook_app = env.remote_account()?.application::<HookAppInterface<_>>()?;
+    let last_hook: Option<HubHookMsg> =
+        hook_app.query(&app::QueryMsg::Module(HookAppQueryMsg::LastHook {}))?;
+    assert_eq!(
+        last_hook,
+        Some(HubHookMsg::OnArrival {
+            token_ids: vec![token_id.clone()],
+            source_chain: "juno".to_string(),
+            msg: to_json_binary(&"arrived")?,
+        })
+    );
+    let transfers = token_transfers(&env.src_hub, &token_id)?;
+    assert_eq!(transfers[0].status, TransferStatus::Completed);
+    assert!(transfers[0].arrival_hook_error.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn supply_follows_the_tokens_across_chains() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let traveller = env.mint()?;
+    let consumable = env.mint()?;
+
+    env.relayed_request(ibc_transfer(&traveller))?;
+    module_request(
+        &env.src_hub,
+        &env.minter,
+        &env.src_account,
+        HubExecuteMsg::Burn {
+            module_id: MINTER_ID.to_string(),
+            token_id: consumable,
+            collection_id: None,
+        },
+    )?;
+
+    let src_supply = env.src_hub.supply()?;
+    assert_eq!(src_supply.chain, "juno");
+    assert_eq!(src_supply.minted, 2);
+    assert_eq!(src_supply.burned, 1);
+    assert_eq!(src_supply.consumed, 1);
+    assert_eq!(src_supply.live, 0);
+
+    let dst_supply = env.dst_hub.supply()?;
+    assert_eq!(dst_supply.chain, "phoenix");
+    assert_eq!(dst_supply.minted, 0);
+    assert_eq!(dst_supply.received, 1);
+    assert_eq!(dst_supply.live, 1);
+
+    Ok(())
+}
+
+#[test]
+fn modules_only_modify_the_allowed_metadata_fields() -> anyhow::Result<()> {
+    let chain = MockBech32::new("mock");
+    let client = setup_adapters(chain.clone())?;
+    let account = setup_account(&client)?;
+    let (hub, minter) = install_hub(&chain, &account)?;
+    let token_id = mint_token(&hub, &minter, &account, None)?;
+    let modify_metadata = |metadata: MetadataPatch| {
+        module_request(
+            &hub,
+            &minter,
+            &account,
+            HubExecuteMsg::ModifyMetadata {
+                module_id: MINTER_ID.to_string(),
+                token_id: token_id.clone(),
+                collection_id: None,
+                metadata,
+            },
+        )
+    };
+    let rename = MetadataPatch {
+        name: Some("Sword".to_string()),
+        ..Default::default()
+    };
+
+    assert!(modify_metadata(rename.clone()).is_err());
+
+    admin_request(
+        &client,
+        &hub,
+        HubExecuteMsg::SetMetadataPermissions {
+            module_id: MINTER_ID.to_string(),
+            fields: vec![MetadataField::Name],
+        },
+    )?;
+    assert_eq!(
+        hub.metadata_permissions(MINTER_ID.to_string())?.fields,
+        vec![MetadataField::Name]
+    );
+    assert!(modify_metadata(MetadataPatch::default()).is_err());
+    modify_metadata(rename)?;
+
+    let nft = get_nft(&hub)?;
+    let metadata = nft.nft_info(token_id.clone())?.extension.unwrap();
+    assert_eq!(metadata.name, Some("Sword".to_string()));
+    assert_eq!(
+        account.proxy()?,
+        nft.owner_of(token_id.clone(), None)?.owner
+    );
+
+    // The other fields are still locked
+    assert!(modify_metadata(MetadataPatch {
+        description: Some("Sharp".to_string()),
+        ..Default::default()
+    })
+    .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn only_the_modules_of_the_owner_burn_its_tokens() -> anyhow::Result<()> {
+    let chain = MockBech32::new("mock");
+    let client = setup_adapters(chain.clone())?;
+    let account = setup_account(&client)?;
+    let (hub, minter) = install_hub(&chain, &account)?;
+    let other_account = setup_account(&client)?;
+    install_hub(&chain, &other_account)?;
+    let token_id = mint_token(&hub, &minter, &account, None)?;
+    let burn = HubExecuteMsg::Burn {
+        module_id: MINTER_ID.to_string(),
+        token_id: token_id.clone(),
+        collection_id: None,
+    };
+
+    // The module has to burn it, on behalf of the account that owns it
+    assert!(account_request(&hub, &account, burn.clone()).is_err());
+    assert!(module_request(&hub, &minter, &other_account, burn.clone()).is_err());
+
+    module_request(&hub, &minter, &account, burn)?;
+    assert!(!hub.token_exists(token_id, None)?.exists);
+    assert_eq!(hub.supply()?.consumed, 1);
+
+    Ok(())
+}
+
+#[test]
+fn collections_are_mirrored_across_chains() -> anyhow::Result<()> {
+    let env = TransferEnv::setup()?;
+    let create_weapons = HubExecuteMsg::CreateCollection {
+        collection_id: "weapons".to_string(),
+        name: "Weapons".to_string(),
+        symbol: "WPN".to_string(),
+        soulbound: false,
+    };
+    admin_request(&env.src_client, &env.src_hub, create_weapons.clone())?;
+    assert!(admin_request(&env.src_client, &env.src_hub, create_weapons).is_err());
+
+    let token_id = env.src_hub.next_token_id()?.next_token_id;
+    let (token_uri, metadata) = nft_metadata();
+    module_request(
+        &env.src_hub,
+        &env.minter,
+        &env.src_account,
+        HubExecuteMsg::Mint {
+            module_id: MINTER_ID.to_string(),
+            collection_id: Some("weapons".to_string()),
+            token_uri,
+            metadata,
+            royalty: None,
+        },
+    )?;
+    assert_eq!(
+        collection_nft(&env.src_hub, "weapons")?.num_tokens()?.count,
+        1
+    );
+    assert_eq!(get_nft(&env.src_hub)?.num_tokens()?.count, 0);
+
+    // Phoenix creates the collection when its first token arrives
+    env.relayed_request(HubExecuteMsg::IbcTransfer {
+        token_id: token_id.clone(),
+        collection_id: Some("weapons".to_string()),
+        recipient_chain: "phoenix".to_string(),
+        recipient: None,
+        fallback_address: None,
+        route: None,
+        on_arrival: None,
+    })?;
+    let dst_collection = env
+        .dst_hub
+        .collections(None, None)?
+        .collections
+        .into_iter()
+        .find(|collection| collection.collection_id == "weapons")
+        .expect("the collection was created");
+    assert_eq!(dst_collection.name, "Weapons");
+    assert_eq!(dst_collection.symbol, "WPN");
+    assert_eq!(
+        env.remote_account()?.proxy()?,
+        collection_nft(&env.dst_hub, "weapons")?
+            .owner_of(token_id, None)?
+            .owner
+    );
+
+    Ok(())
+}
+
+#[test]
+fn collection_names_are_set_at_instantiate() -> anyhow::Result<()> {
+    let chain = MockBech32::new("mock");
+    let client = setup_adapters(chain.clone())?;
+    let account = setup_account(&client)?;
+
+    let hub = instantiate_standalone_hub(
+        &chain,
+        "staging-hub",
+        HubInstantiateMsg {
+            admin_account: account.id()?,
+            nft_code_id: Cw721::new("nft_metadata", chain.clone()).code_id()?,
+            nft_name: "Staging Adventurers".to_string(),
+            nft_symbol: "STG-CA".to_string(),
+            nft_label: "Staging Adventures NFT".to_string(),
+            nft_address: None,
+            transfer_mode: None,
+        },
+    )?;
+
+    let config = hub.config()?;
+    assert_eq!(config.nft_name, "Staging Adventurers");
+    assert_eq!(config.nft_symbol, "STG-CA");
+    assert_eq!(config.nft_label, "Staging Adventures NFT");
+
+    let contract_info = get_nft(&hub)?.contract_info()?;
+    assert_eq!(contract_info.name, "Staging Adventurers");
+    assert_eq!(contract_info.symbol, "STG-CA");
+
+    Ok(())
+}
+
+#[test]
+fn existing_collections_are_adopted() -> anyhow::Result<()> {
+    let chain = MockBech32::new("mock");
+    let client = setup_adapters(chain.clone())?;
+    let account = setup_account(&client)?;
+    let nft_code_id = Cw721::new("nft_metadata", chain.clone()).code_id()?;
+
+    // The existing contract needs the hub as its minter
+    let deploy_nft = |id: &str, minter: &Addr| -> anyhow::Result<Cw721<MockBech32>> {
+        let nft = Cw721::new(id, chain.clone());
+        nft.set_code_id(nft_code_id);
+        nft.instantiate(
+            &cosmos_adventures_nft::msg::InstantiateMsg {
+                name: "Existing Adventurers".to_string(),
+                symbol: "EX-CA".to_string(),
+                minter: minter.to_string(),
+                soulbound: false,
+            },
+            None,
+            None,
+        )?;
+        Ok(nft)
+    };
+    let hub_msg = |nft: &Cw721<MockBech32>| -> anyhow::Result<HubInstantiateMsg> {
+        Ok(HubInstantiateMsg {
+            admin_account: account.id()?,
+            nft_code_id,
+            nft_name: "Cosmos Adventurers".to_string(),
+            nft_symbol: "IBC-CA".to_string(),
+            nft_label: "Cosmos Adventures NFT".to_string(),
+            nft_address: Some(nft.address()?.to_string()),
+            transfer_mode: None,
+        })
+    };
+
+    let foreign_nft = deploy_nft("foreign_nft", &chain.sender())?;
+    assert!(instantiate_standalone_hub(&chain, "foreign-hub", hub_msg(&foreign_nft)?).is_err());
+
+    let existing_nft = deploy_nft(
+        "existing_nft",
+        &standalone_hub_address(&chain, "adopting-hub")?,
+    )?;
+    let hub = instantiate_standalone_hub(&chain, "adopting-hub", hub_msg(&existing_nft)?)?;
+
+    // The collection keeps the name and symbol of the contract
+    let config = hub.config()?;
+    assert_eq!(config.nft, existing_nft.address()?.to_string());
+    assert_eq!(config.nft_name, "Existing Adventurers");
+    assert_eq!(config.nft_symbol, "EX-CA");
+
+    Ok(())
+}
+
+#[test]
+fn admin_role_is_transferred_in_two_steps() -> anyhow::Result<()> {
+    let chain = MockBech32::new("mock");
+    let client = setup_adapters(chain.clone())?;
+    let account = setup_account(&client)?;
+    let (hub, _) = install_hub(&chain, &account)?;
+    let other_account = setup_account(&client)?;
+    other_account.install_adapter::<CosmosAdventuresHub<_>>(&[])?;
+    let old_admin = client.account_from(hub.config()?.admin_account)?;
+
+    // Only the admin can change the hub settings
+    let pause = HubExecuteMsg::SetPaused { paused: true };
+    assert!(account_request(&hub, &account, pause.clone()).is_err());
+
+    admin_request(
+        &client,
+        &hub,
+        HubExecuteMsg::ProposeAdmin {
+            admin_account: account.id()?,
+        },
+    )?;
+    let config = hub.config()?;
+    assert_eq!(config.admin_account, old_admin.id()?);
+    assert_eq!(config.pending_admin_account, Some(account.id()?));
+
+    // Only the proposed account can accept
+    assert!(account_request(&hub, &other_account, HubExecuteMsg::AcceptAdmin {}).is_err());
+    account_request(&hub, &account, HubExecuteMsg::AcceptAdmin {})?;
+
+    let config = hub.config()?;
+    assert_eq!(config.admin_account, account.id()?);
+    assert_eq!(config.pending_admin_account, None);
+    assert!(account_request(&hub, &old_admin, pause.clone()).is_err());
+    account_request(&hub, &account, pause)?;
+    assert!(hub.config()?.paused);
+
+    Ok(())
+}
+
+#[test]
+fn inventory_follows_the_account_tokens() -> anyhow::Result<()> {
+    let chain = MockBech32::new("mock");
+    let client = setup_adapters(chain.clone())?;
+    let account = setup_account(&client)?;
+    let (hub, minter) = install_hub(&chain, &account)?;
+
+    let first_token = mint_token(&hub, &minter, &account, None)?;
+    let second_token = mint_token(&hub, &minter, &account, None)?;
+    let third_token = mint_token(&hub, &minter, &account, None)?;
+    let inventory = hub.account_inventory(account.id()?, None, None)?;
+    let token_ids = inventory
+        .tokens
+        .iter()
+        .map(|token| token.token_id.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        token_ids,
+        vec![
+            first_token.clone(),
+            second_token.clone(),
+            third_token.clone()
+        ]
+    );
+
+    // Burned tokens leave the inventory
+    module_request(
+        &hub,
+        &minter,
+        &account,
+        HubExecuteMsg::Burn {
+            module_id: MINTER_ID.to_string(),
+            token_id: first_token,
+            collection_id: None,
+        },
+    )?;
+    let inventory = hub.account_inventory(account.id()?, None, None)?;
+    assert_eq!(inventory.tokens.len(), 2);
+    assert_eq!(inventory.tokens[0].token_id, second_token);
+
+    // Tokens moved outside the hub aren't listed, even in a page that would end with them
+    get_nft(&hub)?.call_as(&account.proxy()?).execute(
+        &Cw721ExecuteMsg::TransferNft {
+            recipient: chain.addr_make("friend").to_string(),
+            token_id: second_token,
+        },
+        None,
+    )?;
+    let inventory: AccountInventoryResponse =
+        hub.query(&QueryMsg::Module(HubQueryMsg::AccountInventory {
+            account_id: account.id()?,
+            start_after: None,
+            limit: Some(1),
+        }))?;
+    assert_eq!(inventory.tokens.len(), 1);
+    assert_eq!(inventory.tokens[0].token_id, third_token);
+
+    Ok(())
+}
+
 // let account = AbstractAccount::new(&Abstract::load_from(chain.clone())?, account.id()?);
 // let main_account = AbstractAccount::new(
 //     &Abstract::load_from(chain.clone())?,